@@ -4,26 +4,7 @@
 // 2: a in G -> a * id = id * a
 // 3: for some b in G, for all a in G -> a * b = b * a = id
 
-pub trait Monoid {
-    fn one() -> Self;
-    fn ope(&self, rhs: &Self) -> Self;
-}
-
-pub trait Group: Monoid {
-    fn inv(self) -> Self;
-}
-
-pub trait AGroup:
-    std::ops::Add<Output = Self>
-    + std::ops::Sub<Output = Self>
-    + std::ops::Neg<Output = Self>
-    + std::iter::Sum
-where
-    Self: std::marker::Sized,
-{
-    fn zero() -> Self;
-}
-
+#[macro_export]
 macro_rules! agroup {
     (
         $type:ty where [ $( $params:tt )* ];
@@ -50,7 +31,7 @@ macro_rules! agroup {
             }
         }
         impl<$($params)*> std::ops::SubAssign for $type where Self: Clone {
-            fn sub__assign(&mut $self, $rhs: Self) {
+            fn sub_assign(&mut $self, $rhs: Self) {
                 *$self = (*$self).clone() - $rhs;
             }
         }
@@ -64,7 +45,7 @@ macro_rules! agroup {
                 write!(f, "{}", self.0)
             }
         }
-        impl<$($params)*> AGroup for $type {
+        impl<$($params)*> $crate::algebra::AGroup for $type {
             fn zero() -> Self { $zero }
         }
         impl std::str::FromStr for $type {
@@ -82,6 +63,6 @@ macro_rules! agroup {
         neg($self_neg:ident) = $code_neg:block
         $(;)*
     ) => {
-        agroup! { $type where []; zero = $zero; add($self, $y) = $code; neg($self_neg) = $code_neg; }
+        $crate::agroup! { $type where []; zero = $zero; add($self, $y) = $code; neg($self_neg) = $code_neg; }
     };
 }
@@ -1,31 +1,4 @@
-mod algebra {
-    pub trait Monoid {
-        fn one() -> Self;
-        fn ope(&self, rhs: &Self) -> Self;
-    }
-
-    pub trait Act<X> {
-        fn act(&self, rhs: X) -> X;
-    }
-
-    pub trait Group: Monoid {
-        fn inv(self) -> Self;
-    }
-
-    pub trait AGroup:
-        std::ops::Add<Output = Self>
-        + std::ops::Sub<Output = Self>
-        + std::ops::Neg<Output = Self>
-        + std::iter::Sum
-    where
-        Self: std::marker::Sized,
-    {
-        fn zero() -> Self;
-    }
-
-}
-
-use algebra::{Monoid, AGroup};
+use crate::algebra::{Monoid, AGroup};
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct Matrix<K> {
@@ -35,7 +8,7 @@ pub struct Matrix<K> {
 #[macro_export]
 macro_rules! mat {
     ( $( $( $x:expr ),* );* ) => {
-        Matrix::new( vec![ $( vec![ $( $x ),* ] ),* ] )
+        $crate::algebra::matrix::Matrix::new( vec![ $( vec![ $( $x ),* ] ),* ] )
     }
 }
 
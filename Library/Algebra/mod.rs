@@ -0,0 +1,55 @@
+// the canonical algebraic traits shared by every structure in this library
+
+pub trait Monoid {
+    fn one() -> Self;
+    fn ope(&self, rhs: &Self) -> Self;
+}
+
+pub trait Act<X> {
+    fn act(&self, rhs: X) -> X;
+}
+
+pub trait Group: Monoid {
+    fn inv(self) -> Self;
+}
+
+pub trait AGroup:
+    std::ops::Add<Output = Self>
+    + std::ops::Sub<Output = Self>
+    + std::ops::Neg<Output = Self>
+    + std::iter::Sum
+where
+    Self: std::marker::Sized,
+{
+    fn zero() -> Self;
+}
+
+// ----- begin the signed primitive integers as additive groups -----
+macro_rules! impl_additive {
+    ($($t:ty),*) => {
+        $(
+            impl Monoid for $t {
+                fn one() -> Self { 0 }
+                fn ope(&self, rhs: &Self) -> Self { self + rhs }
+            }
+            impl Group for $t {
+                fn inv(self) -> Self { -self }
+            }
+            impl AGroup for $t {
+                fn zero() -> Self { 0 }
+            }
+        )*
+    };
+}
+
+impl_additive!(i32, i64, i128, isize);
+// ----- end the signed primitive integers as additive groups -----
+
+#[path = "monoid.rs"]
+pub mod monoid;
+#[path = "Group.rs"]
+pub mod group;
+#[path = "matrix.rs"]
+pub mod matrix;
+
+pub use monoid::{MinMonoid, MaxMonoid, Rev, RevAct, SumMonoid};
@@ -3,15 +3,9 @@
 // 1: a, b, c in M -> a * (b * c) = (a * b) * c
 // 2: a in M -> a * id = id * a
 
-pub trait Monoid {
-    fn one() -> Self;
-    fn ope(&self, rhs: &Self) -> Self;
-}
-
-pub trait Act<X> {
-    fn act(&self, rhs: X) -> X;
-}
+use crate::algebra::Monoid;
 
+#[macro_export]
 macro_rules! monoid {
     (
         $type:ty where [ $( $params:tt )* ];
@@ -19,7 +13,7 @@ macro_rules! monoid {
         ope($self:ident, $rhs:ident) = $code:block
         $(;)*
     ) => {
-        impl<$($params)*> Monoid for $type {
+        impl<$($params)*> $crate::algebra::Monoid for $type {
             fn one() -> Self { $one }
             fn ope(&$self, $rhs: &Self) -> Self { $code }
         }
@@ -30,7 +24,7 @@ macro_rules! monoid {
         ope($self:ident, $rhs:ident) = $code:block
         $(;)*
     ) => {
-        monoid! {
+        $crate::monoid! {
             $type where [];
             one = $one;
             ope($self, $rhs) = $code
@@ -40,7 +34,7 @@ macro_rules! monoid {
 
 // --- begin MinMonoid ---
 #[derive(Debug, PartialEq, Clone, Copy)]
-pub struct MinMonoid(usize);
+pub struct MinMonoid(pub usize);
 
 impl Monoid for MinMonoid {
     fn one() -> Self {
@@ -69,7 +63,7 @@ impl std::str::FromStr for MinMonoid {
 
 // --- begin MaxMonoid ---
 #[derive(Debug, PartialEq, Clone, Copy)]
-pub struct MaxMonoid(usize);
+pub struct MaxMonoid(pub usize);
 
 impl Monoid for MaxMonoid {
     fn one() -> Self {
@@ -77,7 +71,7 @@ impl Monoid for MaxMonoid {
     }
 
     fn ope(&self, rhs: &Self) -> Self {
-        MaxMonoid(self.0.min(rhs.0))
+        MaxMonoid(self.0.max(rhs.0))
     }
 }
 
@@ -98,7 +92,7 @@ impl std::str::FromStr for MaxMonoid {
 
 // ---begin SumMonoid ---
 #[derive(Debug, PartialEq, Clone, Copy)]
-pub struct SumMonoid(usize);
+pub struct SumMonoid(pub usize);
 
 impl Monoid for SumMonoid {
     fn one() -> Self {
@@ -124,3 +118,39 @@ impl std::str::FromStr for SumMonoid {
     }
 }
 // --- end SumMonoid ---
+
+// --- begin Rev ---
+// X with the operation reversed, so that folding [l, r) gives x[r - 1] * ... * x[l]
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Rev<X>(pub X);
+
+impl<X: Monoid> Monoid for Rev<X> {
+    fn one() -> Self {
+        Rev(X::one())
+    }
+
+    fn ope(&self, rhs: &Self) -> Self {
+        Rev(rhs.0.ope(&self.0))
+    }
+}
+
+// M acting on Rev<X> as it does on X, with the composition of M kept as it is
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct RevAct<M>(pub M);
+
+impl<M: Monoid> Monoid for RevAct<M> {
+    fn one() -> Self {
+        RevAct(M::one())
+    }
+
+    fn ope(&self, rhs: &Self) -> Self {
+        RevAct(self.0.ope(&rhs.0))
+    }
+}
+
+impl<X, M: crate::algebra::Act<X>> crate::algebra::Act<Rev<X>> for RevAct<M> {
+    fn act(&self, rhs: Rev<X>) -> Rev<X> {
+        Rev(self.0.act(rhs.0))
+    }
+}
+// --- end Rev ---
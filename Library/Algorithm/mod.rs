@@ -0,0 +1,2 @@
+#[path = "Mo.rs"]
+pub mod mo;
@@ -0,0 +1,2 @@
+#[path = "LIS.rs"]
+pub mod lis;
@@ -1,156 +1,254 @@
-pub mod algebra {
-    pub trait Monoid {
-        fn one() -> Self;
-        fn ope(&self, rhs: &Self) -> Self;
-    }
+use crate::algebra::*;
+use crate::misc::binary_search::BinarySearch;
+
+// ----- begin Binary Indexed Tree -----
+pub struct BIT<T> {
+    n: usize,
+    data: Vec<T>,
+}
 
-    pub trait Act<X> {
-        fn act(&self, rhs: X) -> X;
+impl<T: Copy + AGroup> BIT<T> {
+    pub fn new(n: usize) -> Self {
+        BIT { n, data: vec![T::zero(); n + 1] }
     }
 
-    pub trait Group: Monoid {
-        fn inv(self) -> Self;
+    // O(n) construction by pushing each node into its parent
+    pub fn from(xs: Vec<T>) -> Self {
+        let n = xs.len();
+        let mut data = vec![T::zero(); n + 1];
+        data[1..].copy_from_slice(&xs);
+        for i in 1..=n {
+            let j = i + (i & i.wrapping_neg());
+            if j <= n {
+                data[j] = data[j] + data[i];
+            }
+        }
+        BIT { n, data }
     }
 
-    pub trait AGroup:
-        std::ops::Add<Output = Self>
-        + std::ops::Sub<Output = Self>
-        + std::ops::Neg<Output = Self>
-        + std::iter::Sum
-    where
-        Self: std::marker::Sized,
-    {
-        fn zero() -> Self;
+    // sum of [0, i)
+    pub fn accum(&self, i: usize) -> T {
+        let mut i = i;
+        let mut ret = T::zero();
+        while i > 0 {
+            ret = ret + self.data[i];
+            i -= i & i.wrapping_neg();
+        }
+        ret
     }
-}
 
-pub mod bit {
-    use crate::algebra::*;
+    // sum of [l, r)
+    pub fn sum(&self, l: usize, r: usize) -> T {
+        if r <= l { return T::zero(); }
+        self.accum(r) - self.accum(l)
+    }
 
-    // ----- begin Algebra implement for i64 -----
-    impl Monoid for i64 {
-        fn one() -> Self {
-            1
-        }
-        fn ope(&self, rhs: &Self) -> Self {
-            self + rhs
-        }
+    pub fn get(&self, i: usize) -> T {
+        self.sum(i, i + 1)
     }
-    impl Group for i64 {
-        fn inv(self) -> Self {
-            -self
+
+    pub fn update(&mut self, i: usize, x: T) {
+        let mut i = i + 1;
+        while i <= self.n {
+            self.data[i] = self.data[i] + x;
+            i += i & i.wrapping_neg();
         }
     }
-    impl AGroup for i64 {
-        fn zero() -> Self {
-            0
+
+    // the smallest i such that sum of [0, i] >= w, or n if there is none
+    // every element must be nonnegative
+    pub fn lower_bound(&self, mut w: T) -> usize
+    where
+        T: PartialOrd
+    {
+        let mut x: usize = 0;
+        let mut k: usize = (self.n + 1).next_power_of_two() / 2;
+        while k > 0 {
+            if x + k <= self.n && self.data[x + k] < w {
+                w = w - self.data[x + k];
+                x += k;
+            }
+            k /= 2;
         }
+        x
     }
-    // ----- end Algebra implement for i64 -----
+}
+// ----- end Binary Indexed Tree -----
+
+// ----- begin Range Binary Indexed Tree -----
+// sum of [0, i) = b1.accum(i) * i + b0.accum(i)
+pub struct RangeBIT<T> {
+    n: usize,
+    b0: BIT<T>,
+    b1: BIT<T>,
+}
 
-    // ----- begin Binary Indexed Tree -----
-    pub struct BIT<T> {
-        n: usize,
-        data: Vec<T>,
+impl<T: Copy + AGroup> RangeBIT<T> {
+    pub fn new(n: usize) -> Self {
+        RangeBIT { n, b0: BIT::new(n + 1), b1: BIT::new(n + 1) }
     }
 
-    impl<T: Copy + AGroup> BIT<T> {
-        pub fn new(n: usize) -> Self {
-            BIT { n, data: vec![T::zero(); n + 1] }
-        }
+    pub fn from(xs: Vec<T>) -> Self {
+        let n = xs.len();
+        let mut ys = xs;
+        ys.push(T::zero());
+        RangeBIT { n, b0: BIT::from(ys), b1: BIT::new(n + 1) }
+    }
 
-        // sum of [0, i)
-        pub fn accum(&self, i: usize) -> T {
-            let mut i = i;
-            let mut ret = T::zero();
-            while i > 0 {
-                ret = ret + self.data[i];
-                i -= i & i.wrapping_neg();
+    // x * k by doubling, since T has no multiplication
+    fn _mul(mut x: T, mut k: usize) -> T {
+        let mut ret = T::zero();
+        while k > 0 {
+            if k & 1 == 1 {
+                ret = ret + x;
             }
-            ret
+            x = x + x;
+            k >>= 1;
         }
+        ret
+    }
 
-        // sum of [l, r)
-        pub fn sum(&self, l: usize, r: usize) -> T {
-            if r <= l { return T::zero(); }
-            self.accum(r) - self.accum(l)
-        }
+    // add x to [l, r)
+    pub fn update(&mut self, l: usize, r: usize, x: T) {
+        assert!(l <= r && r <= self.n);
+        self.b0.update(l, -Self::_mul(x, l));
+        self.b0.update(r, Self::_mul(x, r));
+        self.b1.update(l, x);
+        self.b1.update(r, -x);
+    }
 
-        pub fn get(&self, i: usize) -> T {
-            self.sum(i, i + 1)
-        }
+    // sum of [0, i)
+    pub fn accum(&self, i: usize) -> T {
+        Self::_mul(self.b1.accum(i), i) + self.b0.accum(i)
+    }
 
+    // sum of [l, r)
+    pub fn sum(&self, l: usize, r: usize) -> T {
+        if r <= l { return T::zero(); }
+        self.accum(r) - self.accum(l)
+    }
 
-        pub fn update(&mut self, i: usize, x: T) {
-            let mut i = i + 1;
-            while i <= self.n {
-                self.data[i] = self.data[i] + x;
-                i += i & i.wrapping_neg();
-            }
-        }
+    pub fn get(&self, i: usize) -> T {
+        self.sum(i, i + 1)
+    }
+}
+// ----- end Range Binary Indexed Tree -----
+
+// ----- begin Binary Indexed Tree 2D -----
+pub struct BIT2D<T> {
+    h: usize,
+    w: usize,
+    data: Vec<Vec<T>>,
+}
+
+impl<T: Copy + AGroup> BIT2D<T> {
+    pub fn new(h: usize, w: usize) -> Self {
+        BIT2D { h, w, data: vec![vec![T::zero(); w + 1]; h + 1] }
+    }
 
-        pub fn lower_bound(&mut self, mut w: T) -> usize
-        where
-            T: std::cmp::PartialOrd
-        {
-            let mut x: usize = 0;
-            let mut k: usize = (self.n + 1).next_power_of_two() / 2;
-            while k > 0 {
-                if x + k < self.n && self.data[x + k] < w {
-                    w = w - self.data[x + k];
-                    x += k;
-                }
-                k /= 2;
+    // sum of [0, i) x [0, j)
+    pub fn accum(&self, i: usize, j: usize) -> T {
+        let mut i = i;
+        let mut ret = T::zero();
+        while i > 0 {
+            let mut j = j;
+            while j > 0 {
+                ret = ret + self.data[i][j];
+                j -= j & j.wrapping_neg();
             }
-            x
+            i -= i & i.wrapping_neg();
         }
+        ret
     }
-    // ----- end Binary Indexed Tree -----
 
-    // ----- begin Binary Indexed Tree 2D -----
-    pub struct BIT2D<T> {
-        h: usize,
-        w: usize,
-        data: Vec<Vec<T>>,
+    // sum of [li, ri) x [lj, rj)
+    pub fn sum(&self, li: usize, ri: usize, lj: usize, rj: usize) -> T {
+        if ri <= li || rj <= lj { return T::zero(); }
+        self.accum(ri, rj) - self.accum(ri, lj) - self.accum(li, rj) + self.accum(li, lj)
     }
 
-    impl<T: Copy + AGroup> BIT2D<T> {
-        pub fn new(h: usize, w: usize) -> Self {
-            BIT2D { h, w, data: vec![vec![T::zero()]; h + 1] }
+    pub fn update(&mut self, i: usize, j: usize, x: T) {
+        let mut i = i + 1;
+        while i <= self.h {
+            let mut j = j + 1;
+            while j <= self.w {
+                self.data[i][j] = self.data[i][j] + x;
+                j += j & j.wrapping_neg();
+            }
+            i += i & i.wrapping_neg();
         }
+    }
+}
+// ----- end Binary Indexed Tree 2D -----
+
+// ----- begin Compressed Binary Indexed Tree 2D -----
+// the points to be updated are given in advance, and coordinates can be up to 1e18
+// O(n log n) memory, O(log^2 n) per operation
+pub struct CompressedBIT2D<T> {
+    xs: Vec<i64>,
+    // ys[i]: the sorted y of the points under the node i
+    ys: Vec<Vec<i64>>,
+    data: Vec<Vec<T>>,
+}
 
-        // sum of [0, i) x [0, j)
-        pub fn accum(&mut self, i: usize, j: usize) -> T {
-            let mut i = i;
-            let mut ret = T::zero();
-            while i > 0 {
-                let mut j = j;
-                while j > 0 {
-                    ret = ret + self.data[i][j];
-                    j -= j & j.wrapping_neg();
-                }
-                i -= i & i.wrapping_neg();
+impl<T: Copy + AGroup> CompressedBIT2D<T> {
+    pub fn new(points: &[(i64, i64)]) -> Self {
+        let mut xs: Vec<i64> = points.iter().map(|&(x, _)| x).collect();
+        xs.sort();
+        xs.dedup();
+        let n = xs.len();
+        let mut ys = vec![vec![]; n + 1];
+        for &(x, y) in points {
+            let mut i = xs.lower_bound(x) + 1;
+            while i <= n {
+                ys[i].push(y);
+                i += i & i.wrapping_neg();
             }
-            ret
         }
+        for v in ys.iter_mut() {
+            v.sort();
+            v.dedup();
+        }
+        let data = ys.iter().map(|v| vec![T::zero(); v.len() + 1]).collect();
+        CompressedBIT2D { xs, ys, data }
+    }
 
-        // sum of [li, ri) x [lj, rj)
-        pub fn sum(&mut self, li: usize, ri: usize, lj: usize, rj: usize) -> T {
-            if ri <= li || rj <= lj { return T::zero(); }
-            self.accum(ri, rj) - self.accum(ri, lj) - self.accum(li, rj) + self.accum(li, lj)
+    // (x, y) must be one of the given points
+    pub fn update(&mut self, x: i64, y: i64, w: T) {
+        let mut i = self.xs.lower_bound(x);
+        assert!(i < self.xs.len() && self.xs[i] == x, "POINT NOT GIVEN");
+        i += 1;
+        while i <= self.xs.len() {
+            let mut j = self.ys[i].lower_bound(y);
+            assert!(j < self.ys[i].len() && self.ys[i][j] == y, "POINT NOT GIVEN");
+            j += 1;
+            while j < self.data[i].len() {
+                self.data[i][j] = self.data[i][j] + w;
+                j += j & j.wrapping_neg();
+            }
+            i += i & i.wrapping_neg();
         }
+    }
 
-        pub fn update(&mut self, i: usize, j: usize, x: T) {
-            let mut i = i + 1;
-            while i <= self.h {
-                let mut j = j + 1;
-                while j <= self.w {
-                    self.data[i][j] = self.data[i][j] + x;
-                    j += j & j.wrapping_neg();
-                }
-                i += i & i.wrapping_neg();
+    // sum of (-inf, x) x (-inf, y)
+    pub fn accum(&self, x: i64, y: i64) -> T {
+        let mut i = self.xs.lower_bound(x);
+        let mut ret = T::zero();
+        while i > 0 {
+            let mut j = self.ys[i].lower_bound(y);
+            while j > 0 {
+                ret = ret + self.data[i][j];
+                j -= j & j.wrapping_neg();
             }
+            i -= i & i.wrapping_neg();
         }
+        ret
+    }
+
+    // sum of [xl, xr) x [yl, yr)
+    pub fn sum(&self, xl: i64, xr: i64, yl: i64, yr: i64) -> T {
+        if xr <= xl || yr <= yl { return T::zero(); }
+        self.accum(xr, yr) - self.accum(xr, yl) - self.accum(xl, yr) + self.accum(xl, yl)
     }
-    // ----- end Binary Indexed Tree 2D -----
 }
+// ----- end Compressed Binary Indexed Tree 2D -----
@@ -17,7 +17,7 @@ impl BinaryTrie {
             let j = x >> i & 1;
             if self.child[node][j] == None {
                 self.child[node][j] = Some(self.size());
-                self.child.push(vec![None; 26]);
+                self.child.push(vec![None; 2]);
                 self.is_end.push(false);
                 self.common.push(0);
             }
@@ -0,0 +1,50 @@
+// static range fold for any monoid
+// O(n log n) build, O(1) query
+
+use crate::algebra::Monoid;
+
+#[derive(Clone)]
+pub struct DisjointSparseTable<X> {
+    n: usize,
+    // data[k][i]: for each block of size 2^(k+1) with the center c,
+    // product of [i, c) if i < c, and product of [c, i] otherwise
+    data: Vec<Vec<X>>
+}
+
+impl<X: Copy + Monoid> DisjointSparseTable<X> {
+    pub fn from(xs: Vec<X>) -> Self {
+        let n = xs.len();
+        let mut data = vec![xs.clone()];
+        let mut k = 1;
+        while 1 << k < n {
+            let mut row = xs.clone();
+            let w = 1 << k;
+            for c in (w..n).step_by(2 * w) {
+                for i in (c - w..c - 1).rev() {
+                    row[i] = xs[i].ope(&row[i + 1]);
+                }
+                for i in c + 1..n.min(c + w) {
+                    row[i] = row[i - 1].ope(&xs[i]);
+                }
+            }
+            data.push(row);
+            k += 1;
+        }
+        DisjointSparseTable { n, data }
+    }
+
+    // product of [l, r)
+    pub fn fold(&self, l: usize, r: usize) -> X {
+        assert!(l <= r && r <= self.n);
+        if l == r {
+            return X::one();
+        }
+        let r = r - 1;
+        if l == r {
+            return self.data[0][l];
+        }
+        // the highest differing bit decides the block whose center splits [l, r]
+        let k = (l ^ r).ilog2() as usize;
+        self.data[k][l].ope(&self.data[k][r])
+    }
+}
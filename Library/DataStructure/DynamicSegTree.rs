@@ -0,0 +1,81 @@
+// segment tree over a huge index range [lo, hi)
+// nodes are created on demand, so the memory is O(q log(hi - lo))
+
+use crate::algebra::Monoid;
+
+#[derive(Clone, Copy)]
+struct Node<X> {
+    val: X,
+    ch: [usize; 2]
+}
+
+#[derive(Clone)]
+pub struct DynamicSegTree<X> {
+    lo: i64,
+    hi: i64,
+    root: usize,
+    // nodes[0] is the empty subtree
+    nodes: Vec<Node<X>>
+}
+
+impl<X: Copy + Monoid> DynamicSegTree<X> {
+    pub fn new(lo: i64, hi: i64) -> Self {
+        assert!(lo < hi);
+        DynamicSegTree { lo, hi, root: 0, nodes: vec![Node { val: X::one(), ch: [0, 0] }] }
+    }
+
+    fn _set(&mut self, mut k: usize, l: i64, r: i64, i: i64, x: X, combine: bool) -> usize {
+        if k == 0 {
+            self.nodes.push(self.nodes[0]);
+            k = self.nodes.len() - 1;
+        }
+        if r - l == 1 {
+            self.nodes[k].val = if combine { self.nodes[k].val.ope(&x) } else { x };
+            return k;
+        }
+        let m = l + (r - l) / 2;
+        if i < m {
+            let c = self._set(self.nodes[k].ch[0], l, m, i, x, combine);
+            self.nodes[k].ch[0] = c;
+        } else {
+            let c = self._set(self.nodes[k].ch[1], m, r, i, x, combine);
+            self.nodes[k].ch[1] = c;
+        }
+        let [a, b] = self.nodes[k].ch;
+        self.nodes[k].val = self.nodes[a].val.ope(&self.nodes[b].val);
+        k
+    }
+
+    fn _fold(&self, k: usize, l: i64, r: i64, a: i64, b: i64) -> X {
+        if k == 0 || b <= l || r <= a {
+            return X::one();
+        }
+        if a <= l && r <= b {
+            return self.nodes[k].val;
+        }
+        let m = l + (r - l) / 2;
+        self._fold(self.nodes[k].ch[0], l, m, a, b).ope(&self._fold(self.nodes[k].ch[1], m, r, a, b))
+    }
+
+    // a[i] <- x
+    pub fn update(&mut self, i: i64, x: X) {
+        assert!(self.lo <= i && i < self.hi);
+        self.root = self._set(self.root, self.lo, self.hi, i, x, false);
+    }
+
+    // a[i] <- a[i] * x
+    pub fn apply(&mut self, i: i64, x: X) {
+        assert!(self.lo <= i && i < self.hi);
+        self.root = self._set(self.root, self.lo, self.hi, i, x, true);
+    }
+
+    // product of [l, r)
+    pub fn fold(&self, l: i64, r: i64) -> X {
+        assert!(self.lo <= l && l <= r && r <= self.hi);
+        self._fold(self.root, self.lo, self.hi, l, r)
+    }
+
+    pub fn get(&self, i: i64) -> X {
+        self.fold(i, i + 1)
+    }
+}
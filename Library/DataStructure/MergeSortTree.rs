@@ -1,3 +1,5 @@
+use crate::misc::binary_search::BinarySearch;
+
 const INF: usize = 1 << 60;
 
 pub struct MergeSortTree {
@@ -28,10 +30,7 @@ impl MergeSortTree {
                         if l == j * m + m / 2 {
                             r += 1;
                             data[d][r - 1]
-                        } else if r == (j + 1) * m {
-                            l += 1;
-                            data[d][l - 1]
-                        } else if data[d][l] <= data[d][r] {
+                        } else if r == (j + 1) * m || data[d][l] <= data[d][r] {
                             l += 1;
                             data[d][l - 1]
                         } else {
@@ -40,7 +39,8 @@ impl MergeSortTree {
                         }
                     };
                     data[d - 1][now] = nxt;
-                    cum[d - 1][now + 1] = cum[d - 1][now] + data[d - 1][now];
+                    // the padding INF does not count, or the sums overflow
+                    cum[d - 1][now + 1] = cum[d - 1][now] + if nxt == INF { 0 } else { nxt };
                     now += 1;
                 }
             }
@@ -0,0 +1,108 @@
+// offline dynamic connectivity
+// every edge lives on an interval of time, which is put on a segment tree over time,
+// and a dfs on the tree unions on the way down and undoes them on the way up
+// O(q log q log n)
+
+use crate::data_structure::rollback_union_find::RollbackUnionFind;
+use std::collections::HashMap;
+
+#[derive(Clone, Copy)]
+enum Event {
+    Add(usize, usize),
+    Remove(usize, usize),
+    Query(usize, usize),
+}
+
+pub struct OfflineDynamicConnectivity {
+    n: usize,
+    events: Vec<Event>
+}
+
+impl OfflineDynamicConnectivity {
+    pub fn new(n: usize) -> Self {
+        OfflineDynamicConnectivity { n, events: vec![] }
+    }
+
+    // parallel edges are counted separately
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        self.events.push(Event::Add(u.min(v), u.max(v)));
+    }
+
+    // the edge must be alive
+    pub fn remove_edge(&mut self, u: usize, v: usize) {
+        self.events.push(Event::Remove(u.min(v), u.max(v)));
+    }
+
+    pub fn query(&mut self, u: usize, v: usize) {
+        self.events.push(Event::Query(u, v));
+    }
+
+    // (whether u and v are connected, the number of components) of each query in order
+    pub fn solve(&self) -> Vec<(bool, usize)> {
+        let q = self.events.len();
+        let mut size = 1;
+        while size < q {
+            size <<= 1;
+        }
+        // the edges alive on each node of the segment tree over time
+        let mut edges = vec![vec![]; 2 * size];
+        let mut open: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        let mut put = |mut l: usize, mut r: usize, e: (usize, usize)| {
+            l += size; r += size;
+            while l < r {
+                if l & 1 == 1 { edges[l].push(e); l += 1; }
+                if r & 1 == 1 { r -= 1; edges[r].push(e); }
+                l >>= 1; r >>= 1;
+            }
+        };
+        for (t, &event) in self.events.iter().enumerate() {
+            match event {
+                Event::Add(u, v) => open.entry((u, v)).or_default().push(t),
+                Event::Remove(u, v) => {
+                    let s = open.get_mut(&(u, v)).and_then(|s| s.pop()).expect("REMOVING ABSENT EDGE");
+                    put(s, t, (u, v));
+                },
+                Event::Query(..) => {},
+            }
+        }
+        for (&e, starts) in open.iter() {
+            for &s in starts {
+                put(s, q, e);
+            }
+        }
+
+        let mut uf = RollbackUnionFind::new(self.n);
+        let mut ans = vec![];
+        if q == 0 {
+            return ans;
+        }
+        // (node, whether entering) in preorder
+        let mut stack = vec![(1, true)];
+        while let Some((k, enter)) = stack.pop() {
+            if !enter {
+                for _ in 0..edges[k].len() {
+                    uf.undo();
+                }
+                continue;
+            }
+            // the leaves beyond q have no events
+            let l = k << (size.trailing_zeros() - k.ilog2());
+            if l - size >= q {
+                continue;
+            }
+            for &(u, v) in &edges[k] {
+                uf.union(u, v);
+            }
+            stack.push((k, false));
+            if k >= size {
+                if let Event::Query(u, v) = self.events[k - size] {
+                    ans.push((uf.same(u, v), uf.count()));
+                }
+            } else {
+                stack.push((k << 1 | 1, true));
+                stack.push((k << 1, true));
+            }
+        }
+        ans
+    }
+}
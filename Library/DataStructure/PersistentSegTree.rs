@@ -0,0 +1,80 @@
+// persistent segment tree over a huge index range [lo, hi)
+// every update returns a new root sharing the untouched nodes with the old version
+// the root 0 is the empty tree (all elements are one)
+
+use crate::algebra::Monoid;
+
+#[derive(Clone, Copy)]
+struct Node<X> {
+    val: X,
+    ch: [usize; 2]
+}
+
+#[derive(Clone)]
+pub struct PersistentSegTree<X> {
+    lo: i64,
+    hi: i64,
+    // nodes[0] is the empty subtree
+    nodes: Vec<Node<X>>
+}
+
+impl<X: Copy + Monoid> PersistentSegTree<X> {
+    pub fn new(lo: i64, hi: i64) -> Self {
+        assert!(lo < hi);
+        PersistentSegTree { lo, hi, nodes: vec![Node { val: X::one(), ch: [0, 0] }] }
+    }
+
+    fn _set(&mut self, k: usize, l: i64, r: i64, i: i64, x: X, combine: bool) -> usize {
+        // copy the node instead of modifying it
+        self.nodes.push(self.nodes[k]);
+        let k = self.nodes.len() - 1;
+        if r - l == 1 {
+            self.nodes[k].val = if combine { self.nodes[k].val.ope(&x) } else { x };
+            return k;
+        }
+        let m = l + (r - l) / 2;
+        if i < m {
+            let c = self._set(self.nodes[k].ch[0], l, m, i, x, combine);
+            self.nodes[k].ch[0] = c;
+        } else {
+            let c = self._set(self.nodes[k].ch[1], m, r, i, x, combine);
+            self.nodes[k].ch[1] = c;
+        }
+        let [a, b] = self.nodes[k].ch;
+        self.nodes[k].val = self.nodes[a].val.ope(&self.nodes[b].val);
+        k
+    }
+
+    fn _fold(&self, k: usize, l: i64, r: i64, a: i64, b: i64) -> X {
+        if k == 0 || b <= l || r <= a {
+            return X::one();
+        }
+        if a <= l && r <= b {
+            return self.nodes[k].val;
+        }
+        let m = l + (r - l) / 2;
+        self._fold(self.nodes[k].ch[0], l, m, a, b).ope(&self._fold(self.nodes[k].ch[1], m, r, a, b))
+    }
+
+    // a[i] <- x on the version root, and returns the new root
+    pub fn update(&mut self, root: usize, i: i64, x: X) -> usize {
+        assert!(self.lo <= i && i < self.hi);
+        self._set(root, self.lo, self.hi, i, x, false)
+    }
+
+    // a[i] <- a[i] * x on the version root, and returns the new root
+    pub fn apply(&mut self, root: usize, i: i64, x: X) -> usize {
+        assert!(self.lo <= i && i < self.hi);
+        self._set(root, self.lo, self.hi, i, x, true)
+    }
+
+    // product of [l, r) on the version root
+    pub fn fold(&self, root: usize, l: i64, r: i64) -> X {
+        assert!(self.lo <= l && l <= r && r <= self.hi);
+        self._fold(root, self.lo, self.hi, l, r)
+    }
+
+    pub fn get(&self, root: usize, i: i64) -> X {
+        self.fold(root, i, i + 1)
+    }
+}
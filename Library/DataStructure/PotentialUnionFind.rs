@@ -1,42 +1,72 @@
-pub struct PotentialUnionFind {
+// union find with potentials in a group, not necessarily commutative
+// potential(v) = potential(u) * w is written as union(u, v, w)
+// e.g. i64 for differences, xor for parities, affine maps for x_v = a * x_u + b
+
+use crate::algebra::Group;
+
+// union(u, v, given) contradicts the earlier constraints, which imply existing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Contradiction<G> {
+    pub u: usize,
+    pub v: usize,
+    pub given: G,
+    pub existing: G
+}
+
+pub struct PotentialUnionFind<G> {
     _n: usize,
     par: Vec<i32>,
-    potential: Vec<i64>
+    // potential(v) = potential(par[v]) * diff[v]
+    diff: Vec<G>
 }
 
-impl PotentialUnionFind {
+impl<G: Copy + PartialEq + Group> PotentialUnionFind<G> {
     pub fn new(_n: usize) -> Self {
-        PotentialUnionFind{ _n, par: vec![-1; _n], potential: vec![0; _n] }
+        PotentialUnionFind{ _n, par: vec![-1; _n], diff: vec![G::one(); _n] }
     }
 
     pub fn find(&mut self, v: usize) -> usize {
         if self.par[v] < 0 { return v; }
-        let r = self.find(self.par[v] as usize);
-        self.potential[v] += self.potential[self.par[v] as usize];
+        let p = self.par[v] as usize;
+        let r = self.find(p);
+        self.diff[v] = self.diff[p].ope(&self.diff[v]);
         self.par[v] = r as i32;
-        return self.par[v] as usize;
+        return r;
     }
 
-    pub fn potential(&mut self, v: usize) -> i64 {
+    // relative to the root
+    pub fn potential(&mut self, v: usize) -> G {
         self.find(v);
-        self.potential[v]
+        self.diff[v]
     }
 
-    pub fn union(&mut self, u: usize, v: usize, w: i64) -> bool {
-        let mut w = w + self.potential(u) - self.potential(v);
-        let mut pu = self.find(u);
-        let mut pv = self.find(v);
-        if pu == pv { return false; }
-        if self.par[pu] > self.par[pv] {
-            std::mem::swap(&mut pu, &mut pv);
-            w = -w;
-        }
-        self.par[pu] += self.par[pv];
-        self.par[pv] = pu as i32;
-        self.potential[pv] = w;
-        return true;
+    // w such that potential(v) = potential(u) * w, if u and v are connected
+    pub fn diff(&mut self, u: usize, v: usize) -> Option<G> {
+        if !self.same(u, v) { return None; }
+        Some(self.potential(u).inv().ope(&self.potential(v)))
     }
 
+    // Ok(true) if merged, Ok(false) if already implied, Err if contradicting
+    pub fn union(&mut self, u: usize, v: usize, w: G) -> Result<bool, Contradiction<G>> {
+        if let Some(existing) = self.diff(u, v) {
+            if existing == w { return Ok(false); }
+            return Err(Contradiction { u, v, given: w, existing });
+        }
+        let (pu, pv) = (self.potential(u), self.potential(v));
+        let (ru, rv) = (self.find(u), self.find(v));
+        // potential(rv) = potential(ru) * d
+        let d = pu.ope(&w).ope(&pv.inv());
+        if self.par[ru] <= self.par[rv] {
+            self.par[ru] += self.par[rv];
+            self.par[rv] = ru as i32;
+            self.diff[rv] = d;
+        } else {
+            self.par[rv] += self.par[ru];
+            self.par[ru] = rv as i32;
+            self.diff[ru] = d.inv();
+        }
+        return Ok(true);
+    }
 
     pub fn same(&mut self, u: usize, v: usize) -> bool {
         self.find(u) == self.find(v)
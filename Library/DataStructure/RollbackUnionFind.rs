@@ -0,0 +1,81 @@
+// union by size without path compression, so every union can be undone
+// O(log n) per operation
+
+pub struct RollbackUnionFind {
+    _n: usize,
+    par: Vec<i32>,
+    count: usize,
+    // (root, its old par, the other root, its old par) of each union, None if nothing changed
+    history: Vec<Option<(usize, i32, usize, i32)>>,
+    snap: usize
+}
+
+impl RollbackUnionFind {
+    pub fn new(_n: usize) -> Self {
+        RollbackUnionFind { _n, par: vec![-1; _n], count: _n, history: vec![], snap: 0 }
+    }
+
+    pub fn find(&self, mut v: usize) -> usize {
+        while self.par[v] >= 0 {
+            v = self.par[v] as usize;
+        }
+        v
+    }
+
+    pub fn union(&mut self, u: usize, v: usize) -> bool {
+        let mut pu = self.find(u);
+        let mut pv = self.find(v);
+        if pu == pv {
+            self.history.push(None);
+            return false;
+        }
+        if self.par[pu] > self.par[pv] {
+            std::mem::swap(&mut pu, &mut pv);
+        }
+        self.history.push(Some((pu, self.par[pu], pv, self.par[pv])));
+        self.par[pu] += self.par[pv];
+        self.par[pv] = pu as i32;
+        self.count -= 1;
+        true
+    }
+
+    pub fn same(&self, u: usize, v: usize) -> bool {
+        self.find(u) == self.find(v)
+    }
+
+    pub fn size(&self, v: usize) -> usize {
+        -self.par[self.find(v)] as usize
+    }
+
+    // the number of components
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    // the number of unions done so far, including the ones that merged nothing
+    pub fn time(&self) -> usize {
+        self.history.len()
+    }
+
+    // cancel the last union
+    pub fn undo(&mut self) {
+        let last = self.history.pop().expect("NOTHING TO UNDO");
+        if let Some((pu, par_u, pv, par_v)) = last {
+            self.par[pu] = par_u;
+            self.par[pv] = par_v;
+            self.count += 1;
+        }
+    }
+
+    pub fn snapshot(&mut self) {
+        self.snap = self.history.len();
+    }
+
+    // undo every union after the last snapshot
+    pub fn rollback(&mut self) {
+        assert!(self.snap <= self.history.len(), "SNAPSHOT ALREADY UNDONE");
+        while self.history.len() > self.snap {
+            self.undo();
+        }
+    }
+}
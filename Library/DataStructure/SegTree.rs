@@ -1,96 +1,141 @@
-pub mod algebra {
-    pub trait Monoid {
-        fn one() -> Self;
-        fn ope(&self, rhs: &Self) -> Self;
-    }
+// internal source is written as 1-indexed
+// and you can access as 0-indexed
 
-    pub trait Act<X> {
-        fn act(&self, rhs: X) -> X;
-    }
+use crate::algebra::Monoid;
 
-    pub trait Group: Monoid {
-        fn inv(self) -> Self;
-    }
+#[derive(Clone)]
+pub struct SegTree<X> {
+    size: usize,
+    len: usize,
+    data: Vec<X>
+}
 
-    pub trait AGroup:
-        std::ops::Add<Output = Self>
-        + std::ops::Sub<Output = Self>
-        + std::ops::Neg<Output = Self>
-        + std::iter::Sum
-    where
-        Self: std::marker::Sized,
-    {
-        fn zero() -> Self;
+impl<X> std::ops::Index<usize> for SegTree<X> {
+    type Output = X;
+    fn index(&self, i: usize) -> &Self::Output {
+        &self.data[self.size / 2 + i]
     }
 }
 
-pub mod seg_tree {
-    // internal source is written as 1-indexed
-    // and you can access as 0-indexed
-
-    use crate::algebra::Monoid;
-
-    #[derive(Clone)]
-    pub struct SegTree<X> {
-        size: usize,
-        data: Vec<X>
+impl<X: Copy + Monoid> SegTree<X> {
+    pub fn new(length: usize) -> Self {
+        let mut M = 1;
+        while M <= length {
+            M <<= 1;
+        }
+        let size = M + M;
+        let data = vec![X::one(); size];
+        SegTree { size, len: length, data }
     }
 
-    impl<X> std::ops::Index<usize> for SegTree<X> {
-        type Output = X;
-        fn index(&self, i: usize) -> &Self::Output {
-            &self.data[self.size / 2 + i]
+    pub fn from(xs: Vec<X>) -> Self {
+        let mut tree = Self::new(xs.len());
+        for (i, &x) in xs.iter().enumerate() {
+            tree.data[tree.size / 2 + i] = x;
+        }
+        for i in (1..tree.size / 2).rev() {
+            tree.data[i] = tree.data[i << 1].ope(&tree.data[i << 1 | 1])
         }
+        tree
     }
 
-    impl<X: Copy + Monoid> SegTree<X> {
-        pub fn new(length: usize) -> Self {
-            let mut M = 1;
-            while M <= length {
-                M <<= 1;
-            }
-            let size = M + M;
-            let data = vec![X::one(); size];
-            SegTree { size, data }
+    pub fn update(&mut self, i: usize, x: X) {
+        let mut idx = self.size / 2 + i;
+        self.data[idx] = x;
+        while idx > 1 {
+            idx >>= 1;
+            self.data[idx] = self.data[idx << 1].ope(&self.data[idx << 1 | 1]);
         }
+    }
 
-        pub fn from(xs: Vec<X>) -> Self {
-            let mut tree = Self::new(xs.len());
-            for (i, &x) in xs.iter().enumerate() {
-                tree.data[tree.size / 2 + i] = x;
+    pub fn fold(&self, l: usize, r: usize) -> X {
+        let mut idx_l = self.size / 2 + l;
+        let mut idx_r = self.size / 2 + r;
+        let mut vl = X::one();
+        let mut vr = X::one();
+        while idx_l < idx_r {
+            if idx_l & 1 == 1 {
+                vl = vl.ope(&self.data[idx_l]);
+                idx_l += 1;
             }
-            for i in (1..tree.size / 2).rev() {
-                tree.data[i] = tree.data[i << 1].ope(&tree.data[i << 1 | 1])
+            if idx_r & 1 == 1 {
+                idx_r -= 1;
+                vr = self.data[idx_r].ope(&vr);
             }
-            tree
+            idx_l >>= 1; idx_r >>= 1;
         }
+        vl.ope(&vr)
+    }
 
-        pub fn update(&mut self, i: usize, x: X) {
-            let mut idx = self.size / 2 + i;
-            self.data[idx] = x;
-            while idx > 1 {
+    // the largest r such that pred(fold(l, r)) holds
+    // pred must be monotone and pred(one) must hold
+    pub fn max_right(&self, l: usize, pred: impl Fn(&X) -> bool) -> usize {
+        assert!(l <= self.len);
+        assert!(pred(&X::one()));
+        if l == self.len {
+            return self.len;
+        }
+        let M = self.size / 2;
+        let mut idx = M + l;
+        let mut acc = X::one();
+        loop {
+            while idx & 1 == 0 {
                 idx >>= 1;
-                self.data[idx] = self.data[idx << 1].ope(&self.data[idx << 1 | 1]);
+            }
+            let nxt = acc.ope(&self.data[idx]);
+            if !pred(&nxt) {
+                // descend to the first leaf that breaks pred
+                while idx < M {
+                    idx <<= 1;
+                    let nxt = acc.ope(&self.data[idx]);
+                    if pred(&nxt) {
+                        acc = nxt;
+                        idx += 1;
+                    }
+                }
+                return idx - M;
+            }
+            acc = nxt;
+            idx += 1;
+            if idx & idx.wrapping_neg() == idx {
+                return self.len;
             }
         }
+    }
 
-        pub fn fold(&self, l: usize, r: usize) -> X {
-            let mut idx_l = self.size / 2 + l;
-            let mut idx_r = self.size / 2 + r;
-            let mut vl = X::one();
-            let mut vr = X::one();
-            while idx_l < idx_r {
-                if idx_l & 1 == 1 {
-                    vl = vl.ope(&self.data[idx_l]);
-                    idx_l += 1;
-                }
-                if idx_r & 1 == 1 {
-                    idx_r -= 1;
-                    vr = self.data[idx_r].ope(&vr);
+    // the smallest l such that pred(fold(l, r)) holds
+    // pred must be monotone and pred(one) must hold
+    pub fn min_left(&self, r: usize, pred: impl Fn(&X) -> bool) -> usize {
+        assert!(r <= self.len);
+        assert!(pred(&X::one()));
+        if r == 0 {
+            return 0;
+        }
+        let M = self.size / 2;
+        let mut idx = M + r;
+        let mut acc = X::one();
+        loop {
+            idx -= 1;
+            while idx > 1 && idx & 1 == 1 {
+                idx >>= 1;
+            }
+            let nxt = self.data[idx].ope(&acc);
+            if !pred(&nxt) {
+                // descend to the last leaf that breaks pred
+                while idx < M {
+                    idx = idx << 1 | 1;
+                    let nxt = self.data[idx].ope(&acc);
+                    if pred(&nxt) {
+                        acc = nxt;
+                        idx -= 1;
+                    }
                 }
-                idx_l >>= 1; idx_r >>= 1;
+                return idx + 1 - M;
+            }
+            acc = nxt;
+            if idx & idx.wrapping_neg() == idx {
+                return 0;
             }
-            vl.ope(&vr)
         }
     }
 }
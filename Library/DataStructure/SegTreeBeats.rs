@@ -0,0 +1,247 @@
+// segment tree beats
+// range chmin / chmax / add / assign and range sum / min / max
+// amortized O(log^2 n) per operation
+
+use crate::algebra::Monoid;
+
+const INF: i64 = i64::MAX / 4;
+
+// the result of fold
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SumMinMax {
+    pub sum: i64,
+    pub min: i64,
+    pub max: i64
+}
+
+crate::monoid! {
+    SumMinMax;
+    one = SumMinMax { sum: 0, min: INF, max: -INF };
+    ope(self, rhs) = {
+        SumMinMax { sum: self.sum + rhs.sum, min: self.min.min(rhs.min), max: self.max.max(rhs.max) }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct Node {
+    // the largest, the second largest (strictly), and the count of the largest
+    max1: i64, max2: i64, maxc: i64,
+    // the smallest, the second smallest (strictly), and the count of the smallest
+    min1: i64, min2: i64, minc: i64,
+    sum: i64,
+    len: i64,
+    add: i64
+}
+
+impl Node {
+    fn leaf(x: i64) -> Self {
+        Node { max1: x, max2: -INF, maxc: 1, min1: x, min2: INF, minc: 1, sum: x, len: 1, add: 0 }
+    }
+}
+
+#[derive(Clone)]
+pub struct SegTreeBeats {
+    n: usize,
+    data: Vec<Node>
+}
+
+impl SegTreeBeats {
+    pub fn new(n: usize) -> Self {
+        Self::from(vec![0; n])
+    }
+
+    pub fn from(xs: Vec<i64>) -> Self {
+        let n = xs.len();
+        let mut tree = SegTreeBeats { n, data: vec![Node::leaf(0); 4 * n.max(1)] };
+        if n > 0 {
+            tree._build(1, 0, n, &xs);
+        }
+        tree
+    }
+
+    fn _build(&mut self, k: usize, l: usize, r: usize, xs: &[i64]) {
+        if r - l == 1 {
+            self.data[k] = Node::leaf(xs[l]);
+            return;
+        }
+        let m = (l + r) / 2;
+        self._build(k << 1, l, m, xs);
+        self._build(k << 1 | 1, m, r, xs);
+        self._recalc(k);
+    }
+
+    fn _recalc(&mut self, k: usize) {
+        let a = self.data[k << 1];
+        let b = self.data[k << 1 | 1];
+        let node = &mut self.data[k];
+        node.sum = a.sum + b.sum;
+        node.len = a.len + b.len;
+        if a.max1 > b.max1 {
+            node.max1 = a.max1; node.maxc = a.maxc; node.max2 = a.max2.max(b.max1);
+        } else if a.max1 < b.max1 {
+            node.max1 = b.max1; node.maxc = b.maxc; node.max2 = a.max1.max(b.max2);
+        } else {
+            node.max1 = a.max1; node.maxc = a.maxc + b.maxc; node.max2 = a.max2.max(b.max2);
+        }
+        if a.min1 < b.min1 {
+            node.min1 = a.min1; node.minc = a.minc; node.min2 = a.min2.min(b.min1);
+        } else if a.min1 > b.min1 {
+            node.min1 = b.min1; node.minc = b.minc; node.min2 = a.min1.min(b.min2);
+        } else {
+            node.min1 = a.min1; node.minc = a.minc + b.minc; node.min2 = a.min2.min(b.min2);
+        }
+    }
+
+    fn _apply_add(&mut self, k: usize, x: i64) {
+        let node = &mut self.data[k];
+        node.sum += x * node.len;
+        node.max1 += x;
+        if node.max2 != -INF { node.max2 += x; }
+        node.min1 += x;
+        if node.min2 != INF { node.min2 += x; }
+        node.add += x;
+    }
+
+    // lower the largest values to x, where max2 < x < max1
+    fn _apply_chmin(&mut self, k: usize, x: i64) {
+        let node = &mut self.data[k];
+        node.sum += (x - node.max1) * node.maxc;
+        if node.max1 == node.min1 {
+            node.min1 = x;
+        } else if node.max1 == node.min2 {
+            node.min2 = x;
+        }
+        node.max1 = x;
+    }
+
+    // raise the smallest values to x, where min1 < x < min2
+    fn _apply_chmax(&mut self, k: usize, x: i64) {
+        let node = &mut self.data[k];
+        node.sum += (x - node.min1) * node.minc;
+        if node.min1 == node.max1 {
+            node.max1 = x;
+        } else if node.min1 == node.max2 {
+            node.max2 = x;
+        }
+        node.min1 = x;
+    }
+
+    fn _propagate(&mut self, k: usize) {
+        let add = self.data[k].add;
+        if add != 0 {
+            self._apply_add(k << 1, add);
+            self._apply_add(k << 1 | 1, add);
+            self.data[k].add = 0;
+        }
+        let (max1, min1) = (self.data[k].max1, self.data[k].min1);
+        for c in [k << 1, k << 1 | 1] {
+            if self.data[c].max1 > max1 { self._apply_chmin(c, max1); }
+            if self.data[c].min1 < min1 { self._apply_chmax(c, min1); }
+        }
+    }
+
+    fn _chmin(&mut self, a: usize, b: usize, x: i64, k: usize, l: usize, r: usize) {
+        if b <= l || r <= a || self.data[k].max1 <= x {
+            return;
+        }
+        if a <= l && r <= b && self.data[k].max2 < x {
+            self._apply_chmin(k, x);
+            return;
+        }
+        self._propagate(k);
+        let m = (l + r) / 2;
+        self._chmin(a, b, x, k << 1, l, m);
+        self._chmin(a, b, x, k << 1 | 1, m, r);
+        self._recalc(k);
+    }
+
+    fn _chmax(&mut self, a: usize, b: usize, x: i64, k: usize, l: usize, r: usize) {
+        if b <= l || r <= a || self.data[k].min1 >= x {
+            return;
+        }
+        if a <= l && r <= b && self.data[k].min2 > x {
+            self._apply_chmax(k, x);
+            return;
+        }
+        self._propagate(k);
+        let m = (l + r) / 2;
+        self._chmax(a, b, x, k << 1, l, m);
+        self._chmax(a, b, x, k << 1 | 1, m, r);
+        self._recalc(k);
+    }
+
+    fn _add(&mut self, a: usize, b: usize, x: i64, k: usize, l: usize, r: usize) {
+        if b <= l || r <= a {
+            return;
+        }
+        if a <= l && r <= b {
+            self._apply_add(k, x);
+            return;
+        }
+        self._propagate(k);
+        let m = (l + r) / 2;
+        self._add(a, b, x, k << 1, l, m);
+        self._add(a, b, x, k << 1 | 1, m, r);
+        self._recalc(k);
+    }
+
+    fn _fold(&mut self, a: usize, b: usize, k: usize, l: usize, r: usize) -> SumMinMax {
+        if b <= l || r <= a {
+            return SumMinMax::one();
+        }
+        if a <= l && r <= b {
+            let node = &self.data[k];
+            return SumMinMax { sum: node.sum, min: node.min1, max: node.max1 };
+        }
+        self._propagate(k);
+        let m = (l + r) / 2;
+        self._fold(a, b, k << 1, l, m).ope(&self._fold(a, b, k << 1 | 1, m, r))
+    }
+
+    // a[i] <- min(a[i], x) for i in [l, r)
+    pub fn chmin(&mut self, l: usize, r: usize, x: i64) {
+        assert!(l <= r && r <= self.n);
+        if l < r { self._chmin(l, r, x, 1, 0, self.n); }
+    }
+
+    // a[i] <- max(a[i], x) for i in [l, r)
+    pub fn chmax(&mut self, l: usize, r: usize, x: i64) {
+        assert!(l <= r && r <= self.n);
+        if l < r { self._chmax(l, r, x, 1, 0, self.n); }
+    }
+
+    // a[i] <- a[i] + x for i in [l, r)
+    pub fn add(&mut self, l: usize, r: usize, x: i64) {
+        assert!(l <= r && r <= self.n);
+        if l < r { self._add(l, r, x, 1, 0, self.n); }
+    }
+
+    // a[i] <- x for i in [l, r)
+    pub fn assign(&mut self, l: usize, r: usize, x: i64) {
+        self.chmin(l, r, x);
+        self.chmax(l, r, x);
+    }
+
+    // sum, min and max of [l, r)
+    pub fn fold(&mut self, l: usize, r: usize) -> SumMinMax {
+        assert!(l <= r && r <= self.n);
+        if l == r { return SumMinMax::one(); }
+        self._fold(l, r, 1, 0, self.n)
+    }
+
+    pub fn sum(&mut self, l: usize, r: usize) -> i64 {
+        self.fold(l, r).sum
+    }
+
+    pub fn min(&mut self, l: usize, r: usize) -> i64 {
+        self.fold(l, r).min
+    }
+
+    pub fn max(&mut self, l: usize, r: usize) -> i64 {
+        self.fold(l, r).max
+    }
+
+    pub fn get(&mut self, i: usize) -> i64 {
+        self.fold(i, i + 1).sum
+    }
+}
@@ -0,0 +1,35 @@
+// static range fold for idempotent monoids (a * a = a), e.g. min, max, gcd
+// O(n log n) build, O(1) query
+
+use crate::algebra::Monoid;
+
+#[derive(Clone)]
+pub struct SparseTable<X> {
+    // data[k][i]: product of [i, i + 2^k)
+    data: Vec<Vec<X>>
+}
+
+impl<X: Copy + Monoid> SparseTable<X> {
+    pub fn from(xs: Vec<X>) -> Self {
+        let n = xs.len();
+        let mut data = vec![xs];
+        let mut k = 1;
+        while 1 << k <= n {
+            let prev = &data[k - 1];
+            let row = (0..=n - (1 << k)).map(|i| prev[i].ope(&prev[i + (1 << (k - 1))])).collect();
+            data.push(row);
+            k += 1;
+        }
+        SparseTable { data }
+    }
+
+    // product of [l, r)
+    pub fn fold(&self, l: usize, r: usize) -> X {
+        assert!(l <= r && r <= self.data[0].len());
+        if l == r {
+            return X::one();
+        }
+        let k = (r - l).ilog2() as usize;
+        self.data[k][l].ope(&self.data[k][r - (1 << k)])
+    }
+}
@@ -0,0 +1,38 @@
+#[path = "BIT.rs"]
+pub mod bit;
+#[path = "BinaryTrie.rs"]
+pub mod binary_trie;
+#[path = "Counter.rs"]
+pub mod counter;
+#[path = "Cumsum.rs"]
+pub mod cumsum;
+#[path = "DisjointSparseTable.rs"]
+pub mod disjoint_sparse_table;
+#[path = "DynamicSegTree.rs"]
+pub mod dynamic_seg_tree;
+#[path = "Imos.rs"]
+pub mod imos;
+#[path = "LazySegTree.rs"]
+pub mod lazy_seg_tree;
+#[path = "LinkedList.rs"]
+pub mod linked_list;
+#[path = "MergeSortTree.rs"]
+pub mod merge_sort_tree;
+#[path = "Multiset.rs"]
+pub mod multiset;
+#[path = "OfflineDynamicConnectivity.rs"]
+pub mod offline_dynamic_connectivity;
+#[path = "PersistentSegTree.rs"]
+pub mod persistent_seg_tree;
+#[path = "PotentialUnionFind.rs"]
+pub mod potential_union_find;
+#[path = "RollbackUnionFind.rs"]
+pub mod rollback_union_find;
+#[path = "SegTree.rs"]
+pub mod seg_tree;
+#[path = "SegTreeBeats.rs"]
+pub mod seg_tree_beats;
+#[path = "SparseTable.rs"]
+pub mod sparse_table;
+#[path = "UnionFind.rs"]
+pub mod union_find;
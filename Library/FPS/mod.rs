@@ -0,0 +1,2 @@
+#[path = "FFT.rs"]
+pub mod fft;
@@ -0,0 +1,82 @@
+// flow with a lower and an upper bound on each edge, reduced to maxflow::FlowGraph
+// the excess of the lower bounds is supplied from a new source and drained to a new sink,
+// and a flow is feasible if and only if those edges are saturated
+// a flow from s to t is conserved but at s and t, and its value, the net flow out of s, is nonnegative
+
+use crate::flow::maxflow::max_flow_trait::FlowCapacity;
+use crate::flow::maxflow::FlowGraph;
+
+#[derive(Clone)]
+pub struct LowerBoundFlow<Cap> {
+    n: usize,
+    edges: Vec<(usize, usize, Cap, Cap)>
+}
+
+impl<Cap: FlowCapacity> LowerBoundFlow<Cap> {
+    pub fn new(n: usize) -> Self {
+        LowerBoundFlow { n, edges: vec![] }
+    }
+    // returns the index of the edge
+    pub fn add_edge(&mut self, from: usize, to: usize, lower: Cap, upper: Cap) -> usize {
+        assert!(from.max(to) < self.n, "VERTICES NUMBER PROCEEDING");
+        assert!(Cap::zero() <= lower && lower <= upper, "INVALID BOUNDS");
+        assert!(from != to, "SELF LOOP");
+        self.edges.push((from, to, lower, upper));
+        self.edges.len() - 1
+    }
+    // a feasible flow with the edge t -> s of no bound if st = Some((s, t)),
+    // where the edge i is the i-th edge of the graph and the edge t -> s comes last
+    fn _feasible(&self, st: Option<(usize, usize)>) -> Option<FlowGraph<Cap>> {
+        let n = self.n;
+        let mut fg = FlowGraph::new(n + 2);
+        let mut excess = vec![Cap::zero(); n];
+        for &(u, v, lower, upper) in &self.edges {
+            fg.add_edge(u, v, upper - lower);
+            excess[u] = excess[u] - lower;
+            excess[v] = excess[v] + lower;
+        }
+        if let Some((s, t)) = st {
+            assert!(s.max(t) < n, "VERTICES NUMBER PROCEEDING");
+            fg.add_edge(t, s, Cap::inf());
+        }
+        let mut need = Cap::zero();
+        for v in 0..n {
+            if excess[v] > Cap::zero() {
+                fg.add_edge(n, v, excess[v]);
+                need = need + excess[v];
+            } else if excess[v] < Cap::zero() {
+                fg.add_edge(v, n + 1, Cap::zero() - excess[v]);
+            }
+        }
+        if fg.flow(n, n + 1) == need { Some(fg) } else { None }
+    }
+    // the flow on each edge
+    fn _flows(&self, fg: &FlowGraph<Cap>) -> Vec<Cap> {
+        self.edges.iter().enumerate().map(|(i, e)| e.2 + fg.get_edge(i).3).collect()
+    }
+    // a feasible circulation, or None if there is none
+    pub fn circulation(&self) -> Option<Vec<Cap>> {
+        let fg = self._feasible(None)?;
+        Some(self._flows(&fg))
+    }
+    // the maximum feasible flow from s to t and the flow on each edge, or None if there is none
+    pub fn max_flow(&self, s: usize, t: usize) -> Option<(Cap, Vec<Cap>)> {
+        let mut fg = self._feasible(Some((s, t)))?;
+        let i = self.edges.len();
+        let f = fg.get_edge(i).3;
+        // the edge t -> s is removed so that the rest goes from s to t
+        fg.change_edge(i, Cap::zero(), Cap::zero());
+        let f = f + fg.flow(s, t);
+        Some((f, self._flows(&fg)))
+    }
+    // the minimum feasible flow from s to t and the flow on each edge, or None if there is none
+    pub fn min_flow(&self, s: usize, t: usize) -> Option<(Cap, Vec<Cap>)> {
+        let mut fg = self._feasible(Some((s, t)))?;
+        let i = self.edges.len();
+        let f = fg.get_edge(i).3;
+        fg.change_edge(i, Cap::zero(), Cap::zero());
+        // the value stays nonnegative
+        let f = f - fg.flow_with_limit(t, s, f);
+        Some((f, self._flows(&fg)))
+    }
+}
@@ -1,122 +1,258 @@
-pub mod maxflow {
-
-    pub mod max_flow_trait {
-        pub trait FlowCapacity:
-            Copy + Ord
-            + std::ops::Add<Output = Self>
-            + std::ops::Sub<Output = Self>
-            + std::fmt::Debug
-        {
-            fn zero() -> Self;
-            fn inf() -> Self;
-        }
+pub mod max_flow_trait {
+    pub trait FlowCapacity:
+        Copy + Ord
+        + std::ops::Add<Output = Self>
+        + std::ops::Sub<Output = Self>
+        + std::fmt::Debug
+    {
+        fn zero() -> Self;
+        fn inf() -> Self;
+    }
 
-        impl FlowCapacity for i64 {
-            fn zero() -> i64 { 0_i64 }
-            fn inf() -> i64 { std::i64::MAX }
-        }
+    impl FlowCapacity for i64 {
+        fn zero() -> i64 { 0_i64 }
+        fn inf() -> i64 { i64::MAX }
     }
+}
 
-    use max_flow_trait::*;
+use max_flow_trait::*;
+use crate::graph::Adjacency;
 
-    #[derive(Clone)]
-    pub struct Edge<Cap> {
-        pub to: usize,
-        pub inv: usize,
-        pub cap: Cap
-    }
+#[derive(Clone)]
+pub struct Edge<Cap> {
+    pub to: usize,
+    pub inv: usize,
+    pub cap: Cap
+}
 
-    impl<Cap: FlowCapacity> Edge<Cap> {
-        pub fn new(to: usize, inv: usize, cap: Cap) -> Self {
-            Edge { to, inv, cap }
-        }
-        pub fn add(&mut self, cap: Cap) {
-            self.cap = self.cap + cap;
-        }
-        pub fn sub(&mut self, cap: Cap) {
-            self.cap = self.cap - cap;
-        }
+impl<Cap: FlowCapacity> Edge<Cap> {
+    pub fn new(to: usize, inv: usize, cap: Cap) -> Self {
+        Edge { to, inv, cap }
     }
-
-    #[derive(Clone)]
-    pub struct FlowGraph<Cap> {
-        pub graph: Vec<Vec<Edge<Cap>>>
+    pub fn add(&mut self, cap: Cap) {
+        self.cap = self.cap + cap;
     }
+    pub fn sub(&mut self, cap: Cap) {
+        self.cap = self.cap - cap;
+    }
+}
 
-    impl<Cap: FlowCapacity> FlowGraph<Cap> {
-        pub fn new(n: usize) -> Self {
-            Self { graph: vec![vec![]; n] }
-        }
-        pub fn add_edge(&mut self, from: usize, to: usize, cap: Cap) {
-            assert!(from.max(to) < self.graph.len(), "VERTICES NUMBER PROCEEDING");
-            assert!(cap >= Cap::zero(), "NEGATIVE CAPACITY");
-            assert!(from != to, "SELF LOOP");
-            let x = self.graph[from].len();
-            let y = self.graph[to].len();
-            self.graph[from].push(Edge::new(to, y, cap));
-            self.graph[to].push(Edge::new(from, x, Cap::zero()));
+// the residual graph, where the i-th edge added is graph[u][j] for (u, j) = pos[i],
+// and its flow is the capacity of the reverse edge
+#[derive(Clone)]
+pub struct FlowGraph<Cap> {
+    pub graph: Vec<Vec<Edge<Cap>>>,
+    pos: Vec<(usize, usize)>
+}
+
+impl<Cap: FlowCapacity> FlowGraph<Cap> {
+    pub fn new(n: usize) -> Self {
+        Self { graph: vec![vec![]; n], pos: vec![] }
+    }
+    // the weight of each edge is its capacity
+    pub fn from_graph(G: &impl Adjacency<Cap>) -> Self {
+        let mut fg = Self::new(G.len());
+        for u in 0..G.len() {
+            for &(v, cap) in G.neighbors(u) {
+                fg.add_edge(u, v, cap);
+            }
         }
-        pub fn flow(&mut self, source: usize, sink: usize) -> Cap {
-            let n = self.graph.len();
-            assert!(source.max(sink) < n, "VERTICES NUMBER PROCEEDING");
-            assert!(source != sink, "SELF LOOP");
-            let mut ret = Cap::zero();
-            loop {
-                let level = (|| -> Vec<usize> {
-                    let mut level = vec![0; n];
-                    level[source] = 1;
-                    let mut dq = std::collections::VecDeque::new();
-                    dq.push_back(source);
-                    while let Some(u) = dq.pop_front() {
-                        for e in self.graph[u].iter() {
-                            let v = e.to;
-                            if e.cap > Cap::zero() && level[v] == 0 {
-                                level[v] = level[u] + 1;
-                                if v == sink {
-                                    return level;
-                                }
-                                dq.push_back(v);
+        fg
+    }
+    // returns the index of the edge
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: Cap) -> usize {
+        assert!(from.max(to) < self.graph.len(), "VERTICES NUMBER PROCEEDING");
+        assert!(cap >= Cap::zero(), "NEGATIVE CAPACITY");
+        assert!(from != to, "SELF LOOP");
+        let x = self.graph[from].len();
+        let y = self.graph[to].len();
+        self.graph[from].push(Edge::new(to, y, cap));
+        self.graph[to].push(Edge::new(from, x, Cap::zero()));
+        self.pos.push((from, x));
+        self.pos.len() - 1
+    }
+    // (from, to, cap, flow) of the i-th edge
+    pub fn get_edge(&self, i: usize) -> (usize, usize, Cap, Cap) {
+        let (u, j) = self.pos[i];
+        let e = &self.graph[u][j];
+        let flow = self.graph[e.to][e.inv].cap;
+        (u, e.to, e.cap + flow, flow)
+    }
+    // (from, to, cap, flow) of every edge, in the order added
+    pub fn edges(&self) -> Vec<(usize, usize, Cap, Cap)> {
+        (0..self.pos.len()).map(|i| self.get_edge(i)).collect()
+    }
+    // set the capacity and the flow of the i-th edge, which may break the flow conservation
+    pub fn change_edge(&mut self, i: usize, cap: Cap, flow: Cap) {
+        assert!(Cap::zero() <= flow && flow <= cap, "FLOW OUT OF RANGE");
+        let (u, j) = self.pos[i];
+        let (to, inv) = (self.graph[u][j].to, self.graph[u][j].inv);
+        self.graph[u][j].cap = cap - flow;
+        self.graph[to][inv].cap = flow;
+    }
+    pub fn flow(&mut self, source: usize, sink: usize) -> Cap {
+        self.flow_with_limit(source, sink, Cap::inf())
+    }
+    // stops once the flow reaches limit
+    pub fn flow_with_limit(&mut self, source: usize, sink: usize, limit: Cap) -> Cap {
+        let n = self.graph.len();
+        assert!(source.max(sink) < n, "VERTICES NUMBER PROCEEDING");
+        assert!(source != sink, "SELF LOOP");
+        let mut ret = Cap::zero();
+        while ret < limit {
+            let level = (|| -> Vec<usize> {
+                let mut level = vec![0; n];
+                level[source] = 1;
+                let mut dq = std::collections::VecDeque::new();
+                dq.push_back(source);
+                while let Some(u) = dq.pop_front() {
+                    for e in self.graph[u].iter() {
+                        let v = e.to;
+                        if e.cap > Cap::zero() && level[v] == 0 {
+                            level[v] = level[u] + 1;
+                            if v == sink {
+                                return level;
                             }
+                            dq.push_back(v);
                         }
                     }
-                    level
-                })();
-                if level[sink] == 0 { break; }
-                let mut it = vec![0; n];
-                loop {
-                    let f = self._dfs(sink, source, Cap::inf(), &mut it, &level);
-                    if f == Cap::zero() { break; }
-                    ret = ret + f;
                 }
+                level
+            })();
+            if level[sink] == 0 { break; }
+            let mut it = vec![0; n];
+            while ret < limit {
+                let f = self._dfs(sink, source, limit - ret, &mut it, &level);
+                if f == Cap::zero() { break; }
+                ret = ret + f;
             }
-            ret
         }
-        fn _dfs(&mut self, u: usize, source: usize, cap: Cap, it: &mut Vec<usize>, level: &Vec<usize>) -> Cap {
-            if u == source { return cap; }
-            while let Some((v, inv)) = self.graph[u].get(it[u]).map(|p| (p.to, p.inv)) {
-                if level[v] + 1 == level[u] && self.graph[v][inv].cap > Cap::zero() {
-                    let cap = cap.min(self.graph[v][inv].cap);
-                    let c = self._dfs(v, source, cap, it, level);
-                    if c > Cap::zero() {
-                        self.graph[u][it[u]].add(c);
-                        self.graph[v][inv].sub(c);
-                        return c;
+        ret
+    }
+    // the vertices reachable from source in the residual graph, which form the source side of a minimum cut
+    // after the maximum flow
+    pub fn min_cut(&self, source: usize) -> Vec<bool> {
+        let mut visited = vec![false; self.graph.len()];
+        visited[source] = true;
+        let mut stack = vec![source];
+        while let Some(u) = stack.pop() {
+            for e in &self.graph[u] {
+                if e.cap > Cap::zero() && !visited[e.to] {
+                    visited[e.to] = true;
+                    stack.push(e.to);
+                }
+            }
+        }
+        visited
+    }
+    // the flow from source to sink as paths of vertices with the amount on each,
+    // leaving out the cycles of flow
+    // the flow must be conserved at every vertex but source and sink, which change_edge may break
+    // O(nm)
+    pub fn decompose(&self, source: usize, sink: usize) -> Vec<(Cap, Vec<usize>)> {
+        let n = self.graph.len();
+        let edges = self.edges();
+        let mut inflow = vec![Cap::zero(); n];
+        let mut outflow = vec![Cap::zero(); n];
+        for &(u, v, _, flow) in &edges {
+            outflow[u] = outflow[u] + flow;
+            inflow[v] = inflow[v] + flow;
+        }
+        assert!((0..n).all(|v| v == source || v == sink || inflow[v] == outflow[v]), "FLOW NOT CONSERVED");
+        let mut rest: Vec<Cap> = edges.iter().map(|e| e.3).collect();
+        let mut out = vec![vec![]; n];
+        for (i, e) in edges.iter().enumerate() {
+            out[e.0].push(i);
+        }
+        let mut it = vec![0; n];
+        // the index of each vertex on the current path
+        let mut on_path = vec![usize::MAX; n];
+        let mut paths = vec![];
+        loop {
+            let mut vs = vec![source];
+            let mut es: Vec<usize> = vec![];
+            on_path[source] = 0;
+            let mut u = source;
+            while u != sink {
+                while it[u] < out[u].len() && rest[out[u][it[u]]] == Cap::zero() {
+                    it[u] += 1;
+                }
+                // by the flow conservation, only the source can run out
+                if it[u] == out[u].len() { break; }
+                let i = out[u][it[u]];
+                let v = edges[i].1;
+                if on_path[v] == usize::MAX {
+                    on_path[v] = vs.len();
+                    vs.push(v);
+                    es.push(i);
+                } else {
+                    // cancel the cycle back to v
+                    es.push(i);
+                    let k = on_path[v];
+                    let c = es[k..].iter().map(|&i| rest[i]).min().unwrap();
+                    for &i in &es[k..] {
+                        rest[i] = rest[i] - c;
                     }
+                    for &w in &vs[k + 1..] {
+                        on_path[w] = usize::MAX;
+                    }
+                    vs.truncate(k + 1);
+                    es.truncate(k);
                 }
-                it[u] += 1;
+                u = v;
+            }
+            for &w in &vs {
+                on_path[w] = usize::MAX;
+            }
+            if u != sink { break; }
+            let c = es.iter().map(|&i| rest[i]).min().unwrap();
+            for &i in &es {
+                rest[i] = rest[i] - c;
             }
-            Cap::zero()
+            paths.push((c, vs));
         }
+        paths
     }
-    impl<Cap: FlowCapacity> std::fmt::Debug for FlowGraph<Cap> {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            for u in 0..self.graph.len() {
-                for e in &self.graph[u] {
-                    let inv = &self.graph[e.to][e.inv];
-                    writeln!(f, "{} -> {} (flow: {:?} / {:?})", u, e.to, inv.cap, inv.cap + e.cap).ok();
+    // a path from the sink back to the source along the levels on an explicit stack,
+    // where it[x] points to the edge taken from each x on the stack
+    fn _dfs(&mut self, sink: usize, source: usize, cap: Cap, it: &mut Vec<usize>, level: &Vec<usize>) -> Cap {
+        let mut stack = vec![sink];
+        while let Some(&u) = stack.last() {
+            if u == source {
+                let path = &stack[..stack.len() - 1];
+                let c = path.iter().fold(cap, |c, &x| {
+                    let e = &self.graph[x][it[x]];
+                    c.min(self.graph[e.to][e.inv].cap)
+                });
+                for &x in path {
+                    let (v, inv) = (self.graph[x][it[x]].to, self.graph[x][it[x]].inv);
+                    self.graph[x][it[x]].add(c);
+                    self.graph[v][inv].sub(c);
+                }
+                return c;
+            }
+            if let Some((v, inv)) = self.graph[u].get(it[u]).map(|p| (p.to, p.inv)) {
+                if level[v] + 1 == level[u] && self.graph[v][inv].cap > Cap::zero() {
+                    stack.push(v);
+                } else {
+                    it[u] += 1;
+                }
+            } else {
+                // a dead end is never visited again in this phase
+                stack.pop();
+                if let Some(&p) = stack.last() {
+                    it[p] += 1;
                 }
             }
-            write!(f, "")
         }
+        Cap::zero()
+    }
+}
+impl<Cap: FlowCapacity> std::fmt::Debug for FlowGraph<Cap> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (u, v, cap, flow) in self.edges() {
+            writeln!(f, "{} -> {} (flow: {:?} / {:?})", u, v, flow, cap).ok();
+        }
+        write!(f, "")
     }
 }
@@ -1,155 +1,268 @@
-pub mod min_cost_flow {
-
-    pub mod min_cost_flow_trait {
-        pub trait FlowCapacity<Cost>:
-            Copy + Ord
-            + std::ops::Add<Output = Self>
-            + std::ops::Sub<Output = Self>
-            + std::ops::Mul<Cost, Output = Cost>
-            + std::fmt::Debug
-        {
-            fn zero() -> Self;
-            fn inf() -> Self;
-        }
+pub mod min_cost_flow_trait {
+    pub trait FlowCapacity<Cost>:
+        Copy + Ord
+        + std::ops::Add<Output = Self>
+        + std::ops::Sub<Output = Self>
+        + std::ops::Mul<Cost, Output = Cost>
+        + std::fmt::Debug
+    {
+        fn zero() -> Self;
+        fn inf() -> Self;
+    }
 
-        pub trait FlowCost:
-            Copy + Ord
-            + std::ops::Add<Output = Self>
-            + std::ops::Sub<Output = Self>
-            + std::ops::Mul<Output = Self>
-            + std::ops::Neg<Output = Self>
-            + std::fmt::Debug
-        {
-            fn zero() -> Self;
-            fn inf() -> Self;
-        }
+    pub trait FlowCost:
+        Copy + Ord
+        + std::ops::Add<Output = Self>
+        + std::ops::Sub<Output = Self>
+        + std::ops::Mul<Output = Self>
+        + std::ops::Neg<Output = Self>
+        + std::fmt::Debug
+    {
+        fn zero() -> Self;
+        fn inf() -> Self;
+    }
 
-        impl FlowCapacity<i64> for i64 {
-            fn zero() -> i64 { 0_i64 }
-            fn inf() -> i64 { std::i64::MAX }
-        }
+    impl FlowCapacity<i64> for i64 {
+        fn zero() -> i64 { 0_i64 }
+        fn inf() -> i64 { i64::MAX }
+    }
 
-        impl FlowCost for i64 {
-            fn zero() -> i64 { 0_i64 }
-            fn inf() -> i64 { std::i64::MAX }
-        }
+    impl FlowCost for i64 {
+        fn zero() -> i64 { 0_i64 }
+        fn inf() -> i64 { i64::MAX }
     }
+}
 
-    use min_cost_flow_trait::*;
+use min_cost_flow_trait::*;
+use crate::graph::Adjacency;
 
-    #[derive(Clone)]
-    pub struct Edge<Cap, Cost> {
-        pub to: usize,
-        pub inv: usize,
-        pub cap: Cap,
-        pub cost: Cost,
-        pub is_inv: bool
-    }
+#[derive(Clone)]
+pub struct Edge<Cap, Cost> {
+    pub to: usize,
+    pub inv: usize,
+    pub cap: Cap,
+    pub cost: Cost,
+    pub is_inv: bool
+}
 
-    impl<Cap: FlowCapacity<Cost>, Cost: FlowCost> Edge<Cap, Cost> {
-        pub fn new(to: usize, inv: usize, cap: Cap, cost: Cost, is_inv: bool) -> Self {
-            Edge { to, inv, cap, cost, is_inv }
-        }
-        pub fn add(&mut self, cap: Cap) {
-            self.cap = self.cap + cap;
-        }
-        pub fn sub(&mut self, cap: Cap) {
-            self.cap = self.cap - cap;
-        }
+impl<Cap: FlowCapacity<Cost>, Cost: FlowCost> Edge<Cap, Cost> {
+    pub fn new(to: usize, inv: usize, cap: Cap, cost: Cost, is_inv: bool) -> Self {
+        Edge { to, inv, cap, cost, is_inv }
     }
-
-    #[derive(Clone)]
-    pub struct FlowGraph<Cap, Cost> {
-        pub graph: Vec<Vec<Edge<Cap, Cost>>>
+    pub fn add(&mut self, cap: Cap) {
+        self.cap = self.cap + cap;
     }
+    pub fn sub(&mut self, cap: Cap) {
+        self.cap = self.cap - cap;
+    }
+}
 
-    impl<Cap: FlowCapacity<Cost>, Cost: FlowCost> FlowGraph<Cap, Cost> {
-        pub fn new(n: usize) -> Self {
-            Self { graph: vec![vec![]; n] }
+// the residual graph, where the i-th edge added is graph[u][j] for (u, j) = pos[i],
+// and its flow is the capacity of the reverse edge
+#[derive(Clone)]
+pub struct FlowGraph<Cap, Cost> {
+    pub graph: Vec<Vec<Edge<Cap, Cost>>>,
+    pos: Vec<(usize, usize)>
+}
+
+impl<Cap: FlowCapacity<Cost>, Cost: FlowCost> FlowGraph<Cap, Cost> {
+    pub fn new(n: usize) -> Self {
+        Self { graph: vec![vec![]; n], pos: vec![] }
+    }
+    // the weight of each edge is (capacity, cost)
+    pub fn from_graph(G: &impl Adjacency<(Cap, Cost)>) -> Self {
+        let mut fg = Self::new(G.len());
+        for u in 0..G.len() {
+            for &(v, (cap, cost)) in G.neighbors(u) {
+                fg.add_edge(u, v, cap, cost);
+            }
         }
-        pub fn add_edge(&mut self, from: usize, to: usize, cap: Cap, cost: Cost) {
-            assert!(from.max(to) < self.graph.len(), "VERTICES NUMBER PROCEEDING");
-            assert!(cap >= Cap::zero(), "NEGATIVE CAPACITY");
-            assert!(from != to, "SELF LOOP");
-            let x = self.graph[from].len();
-            let y = self.graph[to].len();
-            self.graph[from].push(Edge::new(to, y, cap, cost, false));
-            self.graph[to].push(Edge::new(from, x, Cap::zero(), -cost, true));
+        fg
+    }
+    // returns the index of the edge, where the cost may be negative
+    pub fn add_edge(&mut self, from: usize, to: usize, cap: Cap, cost: Cost) -> usize {
+        assert!(from.max(to) < self.graph.len(), "VERTICES NUMBER PROCEEDING");
+        assert!(cap >= Cap::zero(), "NEGATIVE CAPACITY");
+        assert!(from != to, "SELF LOOP");
+        let x = self.graph[from].len();
+        let y = self.graph[to].len();
+        self.graph[from].push(Edge::new(to, y, cap, cost, false));
+        self.graph[to].push(Edge::new(from, x, Cap::zero(), -cost, true));
+        self.pos.push((from, x));
+        self.pos.len() - 1
+    }
+    // (from, to, cap, flow, cost) of the i-th edge
+    pub fn get_edge(&self, i: usize) -> (usize, usize, Cap, Cap, Cost) {
+        let (u, j) = self.pos[i];
+        let e = &self.graph[u][j];
+        let flow = self.graph[e.to][e.inv].cap;
+        (u, e.to, e.cap + flow, flow, e.cost)
+    }
+    // (from, to, cap, flow, cost) of every edge, in the order added
+    pub fn edges(&self) -> Vec<(usize, usize, Cap, Cap, Cost)> {
+        (0..self.pos.len()).map(|i| self.get_edge(i)).collect()
+    }
+    pub fn init(&mut self) {
+        for u in 0..self.graph.len() {
+            for i in 0..self.graph[u].len() {
+                if self.graph[u][i].is_inv { continue; }
+                let (to, inv) = (self.graph[u][i].to, self.graph[u][i].inv);
+                let c = self.graph[u][i].cap;
+                let rc = self.graph[to][inv].cap;
+                self.graph[u][i].cap = c + rc;
+                self.graph[to][inv].cap = Cap::zero();
+            }
         }
-        pub fn init(&mut self) {
-            for u in 0..self.graph.len() {
-                for i in 0..self.graph[u].len() {
-                    if self.graph[u][i].is_inv { continue; }
-                    let (to, inv) = (self.graph[u][i].to, self.graph[u][i].inv);
-                    let c = self.graph[u][i].cap;
-                    let rc = self.graph[to][inv].cap;
-                    self.graph[u][i].cap = c + rc;
-                    self.graph[to][inv].cap = Cap::zero();
+    }
+    // the distances from source in the residual graph by Bellman-Ford, which are valid potentials,
+    // or zeros if no residual edge has a negative cost
+    fn _potential(&self, source: usize) -> Vec<Cost> {
+        let n = self.graph.len();
+        let negative = self.graph.iter().flatten().any(|e| e.cap > Cap::zero() && e.cost < Cost::zero());
+        if !negative { return vec![Cost::zero(); n]; }
+        let mut dist = vec![Cost::inf(); n];
+        dist[source] = Cost::zero();
+        for k in 0..=n {
+            let mut updated = false;
+            for u in 0..n {
+                if dist[u] == Cost::inf() { continue; }
+                for e in &self.graph[u] {
+                    if e.cap > Cap::zero() && dist[u] + e.cost < dist[e.to] {
+                        dist[e.to] = dist[u] + e.cost;
+                        updated = true;
+                    }
                 }
             }
+            if !updated { break; }
+            assert!(k < n, "NEGATIVE CYCLE");
         }
-        pub fn min_cost(&mut self, source: usize, sink: usize, flow: Cap) -> Option<Cost> {
-            let n = self.graph.len();
-            assert!(source.max(sink) < n, "VERTICES NUMBER PROCEEDING");
-            assert!(source != sink, "SELF LOOP");
-            let mut ret = Cost::zero();
-            let mut flow = flow;
-            let mut potential = vec![Cost::zero(); n];
-            let mut prevv = vec![!0; n];
-            let mut preve = vec![!0; n];
-            while flow > Cap::zero() {
-                let mut min_cost = vec![Cost::inf(); n];
-                let mut hq = std::collections::BinaryHeap::new();
-                hq.push((std::cmp::Reverse(Cost::zero()), source));
-                min_cost[source] = Cost::zero();
-                while let Some((std::cmp::Reverse(d), u)) = hq.pop() {
-                    if min_cost[u] < d { continue; }
-                    for (i, e) in self.graph[u].iter().enumerate() {
-                        let next_cost = min_cost[u] + e.cost + potential[u] - potential[e.to];
-                        if e.cap > Cap::zero() && min_cost[e.to] > next_cost {
-                            min_cost[e.to] = next_cost;
-                            prevv[e.to] = u;
-                            preve[e.to] = i;
-                            hq.push((std::cmp::Reverse(min_cost[e.to]), e.to));
-                        }
+        // the unreachable vertices stay unreachable
+        dist.iter().map(|&d| if d == Cost::inf() { Cost::zero() } else { d }).collect()
+    }
+    // send up to limit from source to sink along the shortest paths, and returns the breakpoints
+    // (flow, cost) of the minimum cost as a function of the flow, which is piecewise linear and convex,
+    // starting from (0, 0)
+    // the residual graph must have no negative cycle reachable from source
+    pub fn slope(&mut self, source: usize, sink: usize, limit: Cap) -> Vec<(Cap, Cost)> {
+        let n = self.graph.len();
+        assert!(source.max(sink) < n, "VERTICES NUMBER PROCEEDING");
+        assert!(source != sink, "SELF LOOP");
+        let mut potential = self._potential(source);
+        let mut prevv = vec![!0; n];
+        let mut preve = vec![!0; n];
+        let (mut flow, mut cost) = (Cap::zero(), Cost::zero());
+        let mut ret = vec![(flow, cost)];
+        // the cost of a unit on the last path
+        let mut last = None;
+        while flow < limit {
+            let mut min_cost = vec![Cost::inf(); n];
+            let mut hq = std::collections::BinaryHeap::new();
+            hq.push((std::cmp::Reverse(Cost::zero()), source));
+            min_cost[source] = Cost::zero();
+            while let Some((std::cmp::Reverse(d), u)) = hq.pop() {
+                if min_cost[u] < d { continue; }
+                for (i, e) in self.graph[u].iter().enumerate() {
+                    if e.cap == Cap::zero() { continue; }
+                    let next_cost = min_cost[u] + e.cost + potential[u] - potential[e.to];
+                    if min_cost[e.to] > next_cost {
+                        min_cost[e.to] = next_cost;
+                        prevv[e.to] = u;
+                        preve[e.to] = i;
+                        hq.push((std::cmp::Reverse(min_cost[e.to]), e.to));
                     }
                 }
-                if min_cost[sink] == Cost::inf() {
-                    // flow > max_flow
-                    return None;
-                }
-                for u in 0..n {
+            }
+            // flow == max_flow
+            if min_cost[sink] == Cost::inf() { break; }
+            for u in 0..n {
+                if min_cost[u] != Cost::inf() {
                     potential[u] = potential[u] + min_cost[u];
                 }
-                let mut add_flow = flow;
-                let mut now = sink;
-                while now != source {
-                    add_flow = add_flow.min(self.graph[prevv[now]][preve[now]].cap);
-                    now = prevv[now];
-                }
-                flow = flow - add_flow;
-                ret = ret + add_flow * potential[sink];
-                let mut now = sink;
-                while now != source {
-                    let (to, inv) = (self.graph[prevv[now]][preve[now]].to, self.graph[prevv[now]][preve[now]].inv);
-                    let rc = self.graph[to][inv].cap;
-                    self.graph[prevv[now]][preve[now]].cap = self.graph[prevv[now]][preve[now]].cap - add_flow;
-                    self.graph[to][inv].cap = rc + add_flow;
-                    now = prevv[now];
-                }
             }
-            Some(ret)
+            let mut add_flow = limit - flow;
+            let mut now = sink;
+            while now != source {
+                add_flow = add_flow.min(self.graph[prevv[now]][preve[now]].cap);
+                now = prevv[now];
+            }
+            let mut now = sink;
+            while now != source {
+                let (to, inv) = (self.graph[prevv[now]][preve[now]].to, self.graph[prevv[now]][preve[now]].inv);
+                self.graph[prevv[now]][preve[now]].sub(add_flow);
+                self.graph[to][inv].add(add_flow);
+                now = prevv[now];
+            }
+            let unit = potential[sink] - potential[source];
+            flow = flow + add_flow;
+            cost = cost + add_flow * unit;
+            // the same slope as the last segment extends it
+            if last == Some(unit) {
+                ret.pop();
+            }
+            ret.push((flow, cost));
+            last = Some(unit);
         }
+        ret
     }
-    impl<Cap: FlowCapacity<Cost>, Cost: FlowCost> std::fmt::Debug for FlowGraph<Cap, Cost> {
-        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            for u in 0..self.graph.len() {
-                for e in &self.graph[u] {
-                    let inv = &self.graph[e.to][e.inv];
-                    writeln!(f, "{} -> {} (flow: {:?} / {:?}, cost: {:?})", u, e.to, inv.cap, inv.cap + e.cap, inv.cap * e.cost).ok();
+    // the minimum cost to send exactly flow, or None if flow exceeds the maximum flow
+    pub fn min_cost(&mut self, source: usize, sink: usize, flow: Cap) -> Option<Cost> {
+        let (f, c) = *self.slope(source, sink, flow).last().unwrap();
+        if f == flow { Some(c) } else { None }
+    }
+    // cancel the negative cycles in the residual graph one by one, found by Bellman-Ford from every vertex,
+    // which makes the current flow the cheapest among those with the same excess at each vertex,
+    // e.g. the minimum cost circulation from no flow, and returns the change of the cost
+    // O(nm) per cycle
+    pub fn min_cost_circulation(&mut self) -> Cost {
+        let n = self.graph.len();
+        let mut ret = Cost::zero();
+        loop {
+            let mut dist = vec![Cost::zero(); n];
+            let mut prev = vec![(!0, !0); n];
+            let mut last = None;
+            for _ in 0..n {
+                last = None;
+                for u in 0..n {
+                    for (i, e) in self.graph[u].iter().enumerate() {
+                        if e.cap > Cap::zero() && dist[u] + e.cost < dist[e.to] {
+                            dist[e.to] = dist[u] + e.cost;
+                            prev[e.to] = (u, i);
+                            last = Some(e.to);
+                        }
+                    }
                 }
+                if last.is_none() { break; }
             }
-            write!(f, "")
+            // updated in the n-th round, so that going back n times lands on a cycle
+            let Some(mut v) = last else { break; };
+            for _ in 0..n {
+                v = prev[v].0;
+            }
+            let mut cycle = vec![];
+            let mut w = v;
+            loop {
+                cycle.push(prev[w]);
+                w = prev[w].0;
+                if w == v { break; }
+            }
+            let add_flow = cycle.iter().map(|&(u, i)| self.graph[u][i].cap).min().unwrap();
+            let mut unit = Cost::zero();
+            for &(u, i) in &cycle {
+                let (to, inv) = (self.graph[u][i].to, self.graph[u][i].inv);
+                self.graph[u][i].sub(add_flow);
+                self.graph[to][inv].add(add_flow);
+                unit = unit + self.graph[u][i].cost;
+            }
+            ret = ret + add_flow * unit;
+        }
+        ret
+    }
+}
+impl<Cap: FlowCapacity<Cost>, Cost: FlowCost> std::fmt::Debug for FlowGraph<Cap, Cost> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (u, v, cap, flow, cost) in self.edges() {
+            writeln!(f, "{} -> {} (flow: {:?} / {:?}, cost: {:?})", u, v, flow, cap, flow * cost).ok();
         }
+        write!(f, "")
     }
 }
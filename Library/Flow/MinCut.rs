@@ -0,0 +1,122 @@
+// minimum cuts of undirected graphs, where G has both directions of every edge with the same capacity
+// stoer_wagner: the global minimum cut, O(n^3)
+// gomory_hu: a tree whose minimum edge on the path from s to t is the minimum s-t cut,
+// and removing an edge leaves the two sides of such a cut, by n - 1 max flows
+
+use crate::flow::maxflow::max_flow_trait::FlowCapacity;
+use crate::flow::maxflow::FlowGraph;
+use crate::graph::{Adjacency, Graph};
+
+// the value of a global minimum cut and one side of it, where n >= 2
+pub fn stoer_wagner<Cap: FlowCapacity>(G: &impl Adjacency<Cap>) -> (Cap, Vec<bool>) {
+    let n = G.len();
+    assert!(n >= 2, "TOO FEW VERTICES");
+    let mut w = vec![vec![Cap::zero(); n]; n];
+    for u in 0..n {
+        for &(v, c) in G.neighbors(u) {
+            if u != v {
+                w[u][v] = w[u][v] + c;
+            }
+        }
+    }
+    // the original vertices merged into each vertex
+    let mut group: Vec<Vec<usize>> = (0..n).map(|v| vec![v]).collect();
+    let mut active: Vec<usize> = (0..n).collect();
+    let mut best = (Cap::inf(), vec![]);
+    while active.len() > 1 {
+        // the maximum adjacency order, where the last two are merged after the cut of the phase
+        let mut key = vec![Cap::zero(); n];
+        let mut added = vec![false; n];
+        let (mut prev, mut last) = (active[0], active[0]);
+        for _ in 0..active.len() {
+            let v = *active.iter().filter(|&&v| !added[v]).max_by_key(|&&v| key[v]).unwrap();
+            added[v] = true;
+            prev = last;
+            last = v;
+            for &u in &active {
+                if !added[u] {
+                    key[u] = key[u] + w[v][u];
+                }
+            }
+        }
+        if key[last] < best.0 {
+            best = (key[last], group[last].clone());
+        }
+        let moved = std::mem::take(&mut group[last]);
+        group[prev].extend(moved);
+        for &u in &active {
+            w[prev][u] = w[prev][u] + w[last][u];
+            w[u][prev] = w[prev][u];
+        }
+        w[prev][prev] = Cap::zero();
+        active.retain(|&v| v != last);
+    }
+    let mut side = vec![false; n];
+    for v in best.1 {
+        side[v] = true;
+    }
+    (best.0, side)
+}
+
+// the Gomory-Hu tree by Gusfield's algorithm, as an undirected tree on the same vertices
+// weighted by the cut values, e.g. for HLD::from_weighted
+pub fn gomory_hu<Cap: FlowCapacity>(G: &impl Adjacency<Cap>) -> Graph<Cap> {
+    let n = G.len();
+    let mut base = FlowGraph::new(n);
+    for u in 0..n {
+        for &(v, c) in G.neighbors(u) {
+            if u != v {
+                base.add_edge(u, v, c);
+            }
+        }
+    }
+    // the edge (v, parent[v]) of the tree has the weight cap[v]
+    let mut parent = vec![0; n];
+    let mut cap = vec![Cap::zero(); n];
+    for s in 1..n {
+        let t = parent[s];
+        let mut fg = base.clone();
+        let f = fg.flow(s, t);
+        let side = fg.min_cut(s);
+        cap[s] = f;
+        for v in (0..n).filter(|&v| v != s) {
+            if side[v] && parent[v] == t {
+                parent[v] = s;
+            }
+        }
+        // s takes the place of t if the parent of t is on the side of s,
+        // so that every edge of the tree keeps separating the sides of its cut
+        if side[parent[t]] {
+            parent[s] = parent[t];
+            parent[t] = s;
+            cap[s] = cap[t];
+            cap[t] = f;
+        }
+    }
+    let mut tree = Graph::new(n);
+    for v in 1..n {
+        tree.add_undirected_edge(v, parent[v], cap[v]);
+    }
+    tree
+}
+
+// the minimum cut between every pair from the Gomory-Hu tree, where cut[v][v] = Cap::inf()
+// O(n^2)
+pub fn all_pairs_min_cut<Cap: FlowCapacity>(tree: &Graph<Cap>) -> Vec<Vec<Cap>> {
+    let n = tree.len();
+    let mut cut = vec![vec![Cap::inf(); n]; n];
+    for s in 0..n {
+        let mut stack = vec![s];
+        let mut visited = vec![false; n];
+        visited[s] = true;
+        while let Some(u) = stack.pop() {
+            for &(v, c) in tree.neighbors(u) {
+                if visited[v] { continue; }
+                visited[v] = true;
+                cut[s][v] = cut[s][u].min(c);
+                stack.push(v);
+            }
+        }
+    }
+    cut
+}
@@ -0,0 +1,8 @@
+#[path = "LowerBoundFlow.rs"]
+pub mod lower_bound_flow;
+#[path = "MaxFlow.rs"]
+pub mod maxflow;
+#[path = "MinCostFlow.rs"]
+pub mod min_cost_flow;
+#[path = "MinCut.rs"]
+pub mod min_cut;
@@ -1,13 +1,10 @@
 #[derive(Debug, Clone, Copy)]
-pub struct Point(i64, i64);
+pub struct Point(pub i64, pub i64);
 
 impl PartialEq for Point {
     fn eq(&self, rhs: &Point) -> bool {
         self.0 == rhs.0 && self.1 == rhs.1
     }
-    fn ne(&self, rhs: &Point) -> bool {
-        !(self == rhs)
-    }
 }
 impl Eq for Point {}
 impl PartialOrd for Point {
@@ -0,0 +1,8 @@
+#[path = "LatticeP.rs"]
+pub mod lattice_p;
+#[path = "Line.rs"]
+pub mod line;
+#[path = "Polygon.rs"]
+pub mod polygon;
+#[path = "Convex.rs"]
+pub mod convex;
@@ -1,15 +1,12 @@
 pub const EPS: f64 = 1e-8;
 
 #[derive(Debug, Clone, Copy)]
-pub struct Point(f64, f64);
+pub struct Point(pub f64, pub f64);
 
 impl PartialEq for Point {
     fn eq(&self, rhs: &Point) -> bool {
         (self.0 - rhs.0).abs() < EPS && (self.1 - rhs.1).abs() < EPS
     }
-    fn ne(&self, rhs: &Point) -> bool {
-        !(self == rhs)
-    }
 }
 impl Eq for Point {}
 impl PartialOrd for Point {
@@ -101,4 +98,4 @@ impl Point {
     pub fn distance(&self, rhs: &Point) -> f64 {
         (*self - *rhs).norm()
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,10 @@
+#[path = "Point.rs"]
+pub mod point;
+#[path = "Line.rs"]
+pub mod line;
+#[path = "Polygon.rs"]
+pub mod polygon;
+#[path = "Convex.rs"]
+pub mod convex;
+#[path = "Circle.rs"]
+pub mod circle;
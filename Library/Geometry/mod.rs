@@ -0,0 +1,4 @@
+#[path = "Lattice/mod.rs"]
+pub mod lattice;
+#[path = "Real2D/mod.rs"]
+pub mod real2d;
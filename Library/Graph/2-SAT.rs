@@ -1,143 +1,143 @@
-pub mod scc {
-    // nord i belongs to the group cmp[i]
-    // dag is the adjective list of cmp
-
-    pub struct SCC {
-        N: usize,
-        E: Vec<(usize, usize)>,
-        is_calculated: bool,
-        cmp: Vec<usize>,
-        dag: Vec<Vec<usize>>
-    }
-
-    impl SCC {
-        pub fn new(N: usize) -> Self {
-            SCC { N, E: vec![], is_calculated: false, cmp: vec![], dag: vec![] }
-        }
-        #[inline]
-        pub fn add_edge(&mut self, u: usize, v: usize) {
-            self.E.push((u, v));
-            self.is_calculated = false;
-        }
-        pub fn calculate(&mut self) {
-            let mut G = vec![vec![]; self.N];
-            let mut G_rev = vec![vec![]; self.N];
-            for &(u, v) in &self.E {
-                G[u].push(v);
-                G_rev[v].push(u);
-            }
-            let mut idx = vec![];
-            let mut searched = vec![false; self.N];
-            for u in 0..self.N {
-                if !searched[u] {
-                    self._dfs(u, &G, &mut searched, &mut idx);
-                }
-            }
-            searched = vec![false; self.N];
-            self.cmp = vec![self.N; self.N];
-            let mut num = 0;
-            for &u in idx.iter().rev() {
-                if !searched[u] {
-                    self._rdfs(u, &G_rev, &mut searched, num);
-                    num += 1;
-                }
-            }
-            self.dag = vec![vec![]; num];
-            for u in 0..self.N {
-                let u2 = self.cmp[u];
-                for &v in &G[u] {
-                    let v2 = self.cmp[v];
-                    if u2 != v2 {
-                        self.dag[u2].push(v2);
-                    }
-                }
-            }
-            for u in 0..num {
-                self.dag[u].sort();
-                self.dag[u].dedup();
-            }
-            self.is_calculated = true;
-        }
-        pub fn from(N: usize, E: Vec<(usize, usize)>) -> Self {
-            let mut scc = SCC::new(N);
-            scc.E = E;
-            scc.calculate();
-            scc
-        }
-        fn _dfs(
-            &self,
-            u: usize,
-            G: &Vec<Vec<usize>>,
-            searched: &mut Vec<bool>,
-            idx: &mut Vec<usize>
-        ) {
-            searched[u] = true;
-            for &v in &G[u] {
-                if searched[v] { continue; }
-                self._dfs(v, G, searched, idx);
+use crate::graph::{Adjacency, Graph};
+use crate::graph::scc::SCC;
+// the literal x = f is the vertex 2 * x + f of the implication graph
+// the auxiliary variables of at_most_one are also in the graph, so vars maps the variables
+// of the caller to the ones of the graph, and answer and explain only mention the former
+pub struct TwoSAT {
+    N: usize,
+    G: Graph<()>,
+    vars: Vec<usize>,
+    answer: Vec<bool>,
+    conflict: Option<usize>
+}
+impl TwoSAT {
+    pub fn new(N: usize) -> Self {
+        TwoSAT { N, G: Graph::new(N + N), vars: (0..N).collect(), answer: vec![false; N], conflict: None }
+    }
+    // an implication graph built elsewhere, where the literal x = f is the vertex 2 * x + f
+    // and the weights are dropped
+    pub fn from_graph<W>(G: &impl Adjacency<W>) -> Self {
+        assert!(G.len() % 2 == 0, "VERTICES NUMBER MUST BE EVEN");
+        let N = G.len() / 2;
+        let mut ts = TwoSAT::new(N);
+        for u in 0..G.len() {
+            for &(v, _) in G.neighbors(u) {
+                ts.G.add_edge(u, v, ());
             }
-            idx.push(u);
         }
-        fn _rdfs(
-            &mut self,
-            u: usize,
-            G_rev: &Vec<Vec<usize>>,
-            searched: &mut Vec<bool>,
-            num: usize,
-        ) {
-            self.cmp[u] = num;
-            searched[u] = true;
-            for &v in &G_rev[u] {
-                if searched[v] { continue; }
-                self._rdfs(v, G_rev, searched, num);
+        ts
+    }
+    // a new variable, numbered after the existing ones
+    pub fn add_variable(&mut self) -> usize {
+        let x = self._add_variable();
+        self.vars.push(x);
+        self.answer.push(false);
+        self.vars.len() - 1
+    }
+    // x = f or y = g
+    #[inline]
+    pub fn add_clause(&mut self, x: usize, f: bool, y: usize, g: bool) {
+        assert!(x < self.vars.len() && y < self.vars.len());
+        self._add_clause(self.vars[x], f, self.vars[y], g);
+    }
+    // a variable of the graph only
+    fn _add_variable(&mut self) -> usize {
+        self.G.add_vertex();
+        self.G.add_vertex();
+        self.N += 1;
+        self.N - 1
+    }
+    // add_clause on the variables of the graph
+    fn _add_clause(&mut self, x: usize, f: bool, y: usize, g: bool) {
+        self.G.add_edge(2 * x + !f as usize, 2 * y + g as usize, ());
+        self.G.add_edge(2 * y + !g as usize, 2 * x + f as usize, ());
+    }
+    // x = f implies y = g
+    pub fn implies(&mut self, x: usize, f: bool, y: usize, g: bool) {
+        self.add_clause(x, !f, y, g);
+    }
+    // x = y
+    pub fn equal(&mut self, x: usize, y: usize) {
+        self.add_clause(x, false, y, true);
+        self.add_clause(x, true, y, false);
+    }
+    // x != y
+    pub fn xor(&mut self, x: usize, y: usize) {
+        self.add_clause(x, true, y, true);
+        self.add_clause(x, false, y, false);
+    }
+    // x = f
+    pub fn fix(&mut self, x: usize, f: bool) {
+        self.add_clause(x, f, x, f);
+    }
+    // at most one of the literals x = f holds
+    // with a new variable for each prefix, which holds if some literal in the prefix does
+    pub fn at_most_one(&mut self, literals: &[(usize, bool)]) {
+        let mut prev: Option<usize> = None;
+        for &(x, f) in literals {
+            let x = self.vars[x];
+            let p = self._add_variable();
+            self._add_clause(x, !f, p, true);
+            if let Some(q) = prev {
+                self._add_clause(q, false, p, true);
+                self._add_clause(q, false, x, !f);
             }
+            prev = Some(p);
         }
-        #[inline]
-        pub fn cmp(&mut self) -> &Vec<usize> {
-            if !self.is_calculated {
-                self.calculate()
-            }
-            &self.cmp
+    }
+    pub fn satisfiable(&mut self) -> bool {
+        let mut scc = SCC::from_graph(&self.G);
+        let id = scc.cmp();
+        // a variable of the caller if any conflicts, so that explain has one to show
+        self.conflict = self.vars.iter().copied().chain(0..self.N).find(|&x| id[2 * x] == id[2 * x + 1]);
+        if self.conflict.is_some() {
+            return false;
         }
-        #[inline]
-        pub fn dag(&mut self) -> &Vec<Vec<usize>> {
-            if !self.is_calculated {
-                self.calculate()
-            }
-            &self.dag
+        for (a, &x) in self.answer.iter_mut().zip(&self.vars) {
+            *a = id[2 * x] < id[2 * x + 1];
         }
+        true
     }
-}
-
-pub mod two_sat {
-    use crate::scc::SCC;
-    pub struct TwoSAT {
-        N: usize,
-        scc: SCC,
-        answer: Vec<bool>
-    }
-    impl TwoSAT {
-        pub fn new(N: usize) -> Self {
-            TwoSAT { N, scc: SCC::new(N + N), answer: vec![false; N] }
-        }
-        #[inline]
-        pub fn add_clause(&mut self, x: usize, f: bool, y: usize, g: bool) {
-            assert!(x < self.N && y < self.N);
-            self.scc.add_edge(2 * x + !f as usize, 2 * y + g as usize);
-            self.scc.add_edge(2 * y + !g as usize, 2 * x + f as usize);
+    pub fn answer(&self) -> &Vec<bool> {
+        &self.answer
+    }
+    pub fn implication_graph(&self) -> &Graph<()> {
+        &self.G
+    }
+    // after satisfiable() returns false, a variable x with the implications from x = true to x = false
+    // and back to x = true, as the literals (variable, value) on the way
+    // the auxiliary variables are skipped, so each literal implies the next through them
+    // None if only auxiliary variables conflict
+    pub fn explain(&self) -> Option<(usize, Vec<(usize, bool)>)> {
+        let x = self.conflict?;
+        let mut var = vec![None; self.N];
+        for (i, &v) in self.vars.iter().enumerate() {
+            var[v] = Some(i);
         }
-        pub fn satisfiable(&mut self) -> bool {
-            let id = self.scc.cmp();
-            for i in 0..self.N {
-                if id[2 * i] == id[2 * i + 1] {
-                    return false;
+        let y = var[x]?;
+        let mut path = self._path(2 * x + 1, 2 * x);
+        path.extend(self._path(2 * x, 2 * x + 1).into_iter().skip(1));
+        let path = path.into_iter().filter_map(|v| Some((var[v / 2]?, v % 2 == 1))).collect();
+        Some((y, path))
+    }
+    // a shortest path from s to t in the implication graph, which must exist
+    fn _path(&self, s: usize, t: usize) -> Vec<usize> {
+        let mut prev = vec![usize::MAX; self.G.len()];
+        prev[s] = s;
+        let mut queue = std::collections::VecDeque::from([s]);
+        while let Some(u) = queue.pop_front() {
+            for &(v, _) in self.G.neighbors(u) {
+                if prev[v] == usize::MAX {
+                    prev[v] = u;
+                    queue.push_back(v);
                 }
-                self.answer[i] = id[2 * i] < id[2 * i + 1];
             }
-            true
         }
-        pub fn answer(&self) -> &Vec<bool> {
-            &self.answer
+        let mut path = vec![t];
+        while *path.last().unwrap() != s {
+            path.push(prev[*path.last().unwrap()]);
         }
+        path.reverse();
+        path
     }
 }
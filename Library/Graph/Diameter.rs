@@ -1,4 +1,5 @@
 use itertools::Itertools;
+use crate::graph::{Adjacency, Graph};
 
 pub struct Diameter {
     pub D: usize,
@@ -9,24 +10,31 @@ pub struct Diameter {
 }
 impl Diameter {
     pub fn new(G: &Vec<Vec<usize>>) -> Self {
+        Diameter::from_graph(&Graph::from_lists(G))
+    }
+    // G has both directions of every edge, and every edge counts as length 1
+    pub fn from_graph<W>(G: &impl Adjacency<W>) -> Self {
         let n = G.len();
         let mut dist = vec![0; n];
-        Diameter::_dfs(0, 0, &G, &mut dist);
+        Diameter::_bfs(0, 0, G, &mut dist);
         let left = dist.iter().position_max().unwrap();
         let mut from_left = vec![0; n];
-        Diameter::_dfs(left, left, &G, &mut from_left);
+        Diameter::_bfs(left, left, G, &mut from_left);
         let right = from_left.iter().position_max().unwrap();
         let mut from_right = vec![0; n];
-        Diameter::_dfs(right, right, &G, &mut from_right);
+        Diameter::_bfs(right, right, G, &mut from_right);
         Self {
             D: from_left[right], left, right, from_left, from_right
         }
     }
-    fn _dfs(u: usize, par: usize, G: &Vec<Vec<usize>>, dist: &mut Vec<usize>) {
-        for &v in &G[u] {
-            if v == par { continue; }
-            dist[v] = dist[u] + 1;
-            Diameter::_dfs(v, u, G, dist);
+    fn _bfs<W>(root: usize, par: usize, G: &impl Adjacency<W>, dist: &mut Vec<usize>) {
+        let mut queue = std::collections::VecDeque::from([(root, par)]);
+        while let Some((u, par)) = queue.pop_front() {
+            for &(v, _) in G.neighbors(u) {
+                if v == par { continue; }
+                dist[v] = dist[u] + 1;
+                queue.push_back((v, u));
+            }
         }
     }
 }
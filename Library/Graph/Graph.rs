@@ -0,0 +1,156 @@
+// the graphs shared by the graph algorithms
+// neighbors(u) holds (v, w) for each edge u -> v of weight w, and W = () for unweighted graphs
+// Graph keeps a list per vertex and can grow, CsrGraph packs them into one array once built
+
+use crate::algebra::AGroup;
+
+// the additive groups of the algebra module with an order, where zero() comes from AGroup
+pub trait Weight: AGroup + Copy + Ord + std::fmt::Debug {
+    // unreachable; a quarter of MAX, so that two finite weights never overflow when added
+    fn inf() -> Self;
+}
+
+macro_rules! impl_weight {
+    ($($t:ty),*) => {
+        $(
+            impl Weight for $t {
+                fn inf() -> Self { <$t>::MAX / 4 }
+            }
+        )*
+    };
+}
+
+impl_weight!(i32, i64, i128, isize);
+
+// what the algorithms take, so that either backend works
+// len and neighbors of both graphs are only here, so this trait must be in scope
+pub trait Adjacency<W> {
+    fn len(&self) -> usize;
+    fn neighbors(&self, u: usize) -> &[(usize, W)];
+}
+
+#[derive(Debug, Clone)]
+pub struct Graph<W> {
+    n: usize,
+    adj: Vec<Vec<(usize, W)>>
+}
+
+impl<W: Copy> Graph<W> {
+    pub fn new(n: usize) -> Self {
+        Graph { n, adj: vec![vec![]; n] }
+    }
+
+    pub fn from_edges(n: usize, E: &[(usize, usize, W)]) -> Self {
+        let mut graph = Graph::new(n);
+        for &(u, v, w) in E {
+            graph.add_edge(u, v, w);
+        }
+        graph
+    }
+
+    pub fn from_undirected_edges(n: usize, E: &[(usize, usize, W)]) -> Self {
+        let mut graph = Graph::new(n);
+        for &(u, v, w) in E {
+            graph.add_undirected_edge(u, v, w);
+        }
+        graph
+    }
+
+    // the index of the new vertex
+    pub fn add_vertex(&mut self) -> usize {
+        self.adj.push(vec![]);
+        self.n += 1;
+        self.n - 1
+    }
+
+    pub fn add_edge(&mut self, u: usize, v: usize, w: W) {
+        assert!(u.max(v) < self.n, "VERTICES NUMBER PROCEEDING");
+        self.adj[u].push((v, w));
+    }
+
+    pub fn add_undirected_edge(&mut self, u: usize, v: usize, w: W) {
+        self.add_edge(u, v, w);
+        self.add_edge(v, u, w);
+    }
+
+    // (u, v, w) of every edge
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, W)> + '_ {
+        (0..self.n).flat_map(move |u| self.adj[u].iter().map(move |&(v, w)| (u, v, w)))
+    }
+}
+
+impl<W: Copy> Adjacency<W> for Graph<W> {
+    fn len(&self) -> usize { self.n }
+    fn neighbors(&self, u: usize) -> &[(usize, W)] { &self.adj[u] }
+}
+
+impl Graph<()> {
+    // from the adjacency lists of an unweighted graph
+    pub fn from_lists(G: &[Vec<usize>]) -> Self {
+        Graph { n: G.len(), adj: G.iter().map(|vs| vs.iter().map(|&v| (v, ())).collect()).collect() }
+    }
+
+    // m edges "u v" from the standard input
+    pub fn read(n: usize, m: usize, directed: bool, one_indexed: bool) -> Self {
+        proconio::input! { E: [(usize, usize); m] }
+        let E: Vec<_> = E.into_iter().map(|(u, v)| (u - one_indexed as usize, v - one_indexed as usize, ())).collect();
+        if directed { Graph::from_edges(n, &E) } else { Graph::from_undirected_edges(n, &E) }
+    }
+}
+
+impl<W: Copy + proconio::source::Readable<Output = W>> Graph<W> {
+    // m edges "u v w" from the standard input
+    pub fn read_weighted(n: usize, m: usize, directed: bool, one_indexed: bool) -> Self {
+        proconio::input! { E: [(usize, usize, W); m] }
+        let E: Vec<_> = E.into_iter().map(|(u, v, w)| (u - one_indexed as usize, v - one_indexed as usize, w)).collect();
+        if directed { Graph::from_edges(n, &E) } else { Graph::from_undirected_edges(n, &E) }
+    }
+}
+
+// compressed sparse row: the neighbors of u are to[start[u]..start[u + 1]], in the order added
+#[derive(Debug, Clone)]
+pub struct CsrGraph<W> {
+    start: Vec<usize>,
+    to: Vec<(usize, W)>
+}
+
+impl<W: Copy> CsrGraph<W> {
+    pub fn from_edges(n: usize, E: &[(usize, usize, W)]) -> Self {
+        let mut start = vec![0; n + 1];
+        for &(u, v, _) in E {
+            assert!(u.max(v) < n, "VERTICES NUMBER PROCEEDING");
+            start[u + 1] += 1;
+        }
+        for i in 0..n {
+            start[i + 1] += start[i];
+        }
+        // counting sort of the edges by u, which keeps the order within each u
+        let mut pos = start.clone();
+        let mut idx = vec![0; E.len()];
+        for (i, &(u, _, _)) in E.iter().enumerate() {
+            idx[pos[u]] = i;
+            pos[u] += 1;
+        }
+        let to = idx.iter().map(|&i| (E[i].1, E[i].2)).collect();
+        CsrGraph { start, to }
+    }
+
+    pub fn from_undirected_edges(n: usize, E: &[(usize, usize, W)]) -> Self {
+        let both: Vec<_> = E.iter().flat_map(|&(u, v, w)| [(u, v, w), (v, u, w)]).collect();
+        CsrGraph::from_edges(n, &both)
+    }
+
+    pub fn from_graph(G: &Graph<W>) -> Self {
+        CsrGraph::from_edges(G.len(), &G.edges().collect::<Vec<_>>())
+    }
+
+    // (u, v, w) of every edge
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize, W)> + '_ {
+        (0..self.len()).flat_map(move |u| self.neighbors(u).iter().map(move |&(v, w)| (u, v, w)))
+    }
+}
+
+impl<W: Copy> Adjacency<W> for CsrGraph<W> {
+    fn len(&self) -> usize { self.start.len() - 1 }
+    fn neighbors(&self, u: usize) -> &[(usize, W)] { &self.to[self.start[u]..self.start[u + 1]] }
+}
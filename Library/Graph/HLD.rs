@@ -1,174 +1,207 @@
-pub mod directed_tree {
-    #[derive(Debug, Clone)]
-    pub struct DirectedTree<T> {
-        n: usize,
-        root: usize,
-        children: Vec<Vec<(usize, T)>>,
-        parents: Vec<Option<(usize, T)>>
-    }
+use crate::algebra::*;
+use crate::data_structure::lazy_seg_tree::LazySegTree;
+use crate::data_structure::seg_tree::SegTree;
+use crate::graph::directed_tree::*;
+use crate::graph::Adjacency;
 
-    impl<T: Copy> DirectedTree<T> {
-        pub fn new(root: usize, n: usize, E: &Vec<(usize, usize, T)>) -> Self {
-            let mut G = vec![vec![]; n];
-            for &(u, v, d) in E {
-                G[u].push((v, d));
-                G[v].push((u, d));
-            }
-            let mut children = vec![vec![]; n];
-            let mut parents = vec![None; n];
-            DirectedTree::_dfs(root, &G, &mut children, &mut parents);
-            DirectedTree { n, root, children, parents }
+pub struct HLD {
+    pub seq: Vec<usize>,
+    pub tree: DirectedTreeNorm,
+    pub subtree_size: Vec<usize>,
+    pub heavy: Vec<Option<usize>>,
+    pub seq_head: Vec<usize>,
+    pub t_in: Vec<usize>,
+    pub t_out: Vec<usize>
+}
+
+impl HLD {
+    pub fn new(root: usize, n: usize, E: &Vec<(usize, usize)>) -> Self {
+        let tree = DirectedTreeNorm::new(root, n, E);
+        HLD::from(&tree)
+    }
+    // G has both directions of every edge
+    pub fn from_graph<W>(root: usize, G: &impl Adjacency<W>) -> Self {
+        HLD::from(&DirectedTreeNorm::from_graph(root, G))
+    }
+    // the weight of the edge (parent(v), v) is put on v
+    pub fn from_weighted<T: Copy>(tree: &DirectedTree<T>) -> Self {
+        HLD::from(&tree.into_norm())
+    }
+    pub fn from(tree: &DirectedTreeNorm) -> Self {
+        let mut hld = HLD {
+            seq: vec![],
+            tree: tree.clone(),
+            subtree_size: vec![0; tree.len()],
+            heavy: vec![None; tree.len()],
+            seq_head: vec![!0; tree.len()],
+            t_in: vec![0; tree.len()],
+            t_out: vec![0; tree.len()]
+        };
+        hld._dfs_size(tree);
+        hld.seq_head[tree.root()] = tree.root();
+        hld._dfs_hld(tree);
+        hld
+    }
+    fn _dfs_size(&mut self, tree: &DirectedTreeNorm) {
+        // children after parents, so the reversed order is a post order
+        let mut order = vec![tree.root()];
+        for i in 0..tree.len() {
+            let u = order[i];
+            order.extend(tree.children(u));
+        }
+        for &u in order.iter().rev() {
+            self.subtree_size[u] = 1 + tree.children(u).map(|&v| self.subtree_size[v]).sum::<usize>();
+            self.heavy[u] = tree.children(u).max_by_key(|&&i| self.subtree_size[i]).copied();
         }
-        fn _dfs(u: usize,
-                G: &Vec<Vec<(usize, T)>>,
-                children: &mut Vec<Vec<(usize, T)>>,
-                parents: &mut Vec<Option<(usize, T)>>)
-        {
-            for &(v, d) in &G[u] {
-                if let Some((p, _)) = parents[u] {
-                    if v == p { continue; }
+    }
+    fn _dfs_hld(&mut self, tree: &DirectedTreeNorm) {
+        let mut stack = vec![tree.root()];
+        while let Some(u) = stack.pop() {
+            self.t_in[u] = self.seq.len();
+            self.t_out[u] = self.t_in[u] + self.subtree_size[u];
+            self.seq.push(u);
+            if let Some(h) = self.heavy[u] {
+                self.seq_head[h] = self.seq_head[u];
+                // the heavy child is visited first, then the others in order
+                for &v in tree.children(u).rev().filter(|&&v| v != h) {
+                    self.seq_head[v] = v;
+                    stack.push(v);
                 }
-                parents[v] = Some((u, d));
-                children[u].push((v, d));
-                DirectedTree::_dfs(v, G, children, parents);
+                stack.push(h);
             }
         }
-        pub fn root(&self) -> usize { self.root }
-        pub fn len(&self) -> usize { self.n }
-        pub fn parent(&self, v: usize) -> Option<(usize, T)> { self.parents[v] }
-        pub fn children(&self, v: usize) -> std::slice::Iter<(usize, T)> { self.children[v].iter() }
-        pub fn into_norm(&self) -> DirectedTreeNorm {
-            let mut children = vec![];
-            let mut parents = vec![];
-            for i in 0..self.n {
-                children.push(self.children[i].iter().map(|&u| u.0).collect::<Vec<usize>>());
-                if let Some(u) = self.parents[i] {
-                    parents.push(Some(u.0))
+    }
+    pub fn sequence(&self) -> std::slice::Iter<'_, usize> { self.seq.iter() }
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut u, mut v) = (u, v);
+        while u != v {
+            if self.t_in[u] > self.t_in[v] { std::mem::swap(&mut u, &mut v); }
+            if self.seq_head[u] == self.seq_head[v] { break; }
+            v = self.tree.parent(self.seq_head[v]).unwrap();
+        }
+        u
+    }
+    // the position of v in seq
+    pub fn index(&self, v: usize) -> usize { self.t_in[v] }
+    // the edge weights in seq order, where each weight is put on the child and the root gets root_val
+    pub fn edge_seq<T: Copy>(&self, tree: &DirectedTree<T>, root_val: T) -> Vec<T> {
+        self.seq.iter().map(|&v| tree.parent(v).map_or(root_val, |(_, w)| w)).collect()
+    }
+    // the subtree of v is [l, r) of seq
+    pub fn subtree_interval(&self, v: usize) -> (usize, usize) {
+        (self.t_in[v], self.t_out[v])
+    }
+    // the path from u to v as intervals [l, r) of seq in order from u to v,
+    // where reversed intervals are walked from r - 1 down to l
+    // with edge = true, the lca is excluded so that the path covers the edges put on children
+    pub fn path_intervals(&self, u: usize, v: usize, edge: bool) -> Vec<(usize, usize, bool)> {
+        let (mut u, mut v) = (u, v);
+        let mut up = vec![];
+        let mut down = vec![];
+        loop {
+            let (hu, hv) = (self.seq_head[u], self.seq_head[v]);
+            if hu == hv {
+                if self.t_in[u] <= self.t_in[v] {
+                    down.push((self.t_in[u] + edge as usize, self.t_in[v] + 1, false));
                 } else {
-                    parents.push(None)
+                    up.push((self.t_in[v] + edge as usize, self.t_in[u] + 1, true));
                 }
+                break;
             }
-            DirectedTreeNorm {
-                n: self.n,
-                root: self.root,
-                children,
-                parents
+            if self.t_in[hu] > self.t_in[hv] {
+                up.push((self.t_in[hu], self.t_in[u] + 1, true));
+                u = self.tree.parent(hu).unwrap();
+            } else {
+                down.push((self.t_in[hv], self.t_in[v] + 1, false));
+                v = self.tree.parent(hv).unwrap();
             }
         }
+        up.extend(down.into_iter().rev());
+        up.retain(|&(l, r, _)| l < r);
+        up
     }
-
-    #[derive(Debug, Clone)]
-    pub struct DirectedTreeNorm {
-        n: usize,
-        root: usize,
-        children: Vec<Vec<usize>>,
-        parents: Vec<Option<usize>>
+    // fold of the path from u to v in this order
+    pub fn path_fold<X: Copy + Monoid>(&self, seg: &PathSegTree<X>, u: usize, v: usize, edge: bool) -> X {
+        self.path_intervals(u, v, edge).iter().fold(X::one(), |acc, &(l, r, rev)| {
+            acc.ope(&if rev { seg.rseg.fold(l, r).0 } else { seg.seg.fold(l, r) })
+        })
     }
-
-    impl DirectedTreeNorm {
-        pub fn new(root: usize, n: usize, E: &Vec<(usize, usize)>) -> Self {
-            let mut G = vec![vec![]; n];
-            for &(u, v) in E {
-                G[u].push(v);
-                G[v].push(u);
-            }
-            let mut children = vec![vec![]; n];
-            let mut parents = vec![None; n];
-            DirectedTreeNorm::_dfs(root, &G, &mut children, &mut parents);
-            DirectedTreeNorm { n, root, children, parents }
-        }
-        fn _dfs(u: usize,
-                G: &Vec<Vec<usize>>,
-                children: &mut Vec<Vec<usize>>,
-                parents: &mut Vec<Option<usize>>)
-        {
-            for &v in &G[u] {
-                if Some(v) == parents[u] { continue; }
-                parents[v] = Some(u);
-                children[u].push(v);
-                DirectedTreeNorm::_dfs(v, G, children, parents);
-            }
-        }
-        pub fn from_P(n: usize, P: &Vec<usize>) -> Self {
-            let mut parents = vec![None; n];
-            let mut children = vec![vec![]; n];
-            for (i, &p) in P.iter().enumerate() {
-                parents[i + 1] = Some(p);
-                children[p].push(i + 1);
-            }
-            DirectedTreeNorm { n, root: 0, children, parents }
+    // path_fold with a single segment tree, where X must be commutative
+    pub fn path_fold_commutative<X: Copy + Monoid>(&self, seg: &SegTree<X>, u: usize, v: usize, edge: bool) -> X {
+        self.path_intervals(u, v, edge).iter().fold(X::one(), |acc, &(l, r, _)| acc.ope(&seg.fold(l, r)))
+    }
+    // the same as path_fold with the actions of path_update
+    pub fn path_fold_lazy<X, M>(&self, seg: &mut PathLazySegTree<X, M>, u: usize, v: usize, edge: bool) -> X
+    where
+        X: Copy + Monoid,
+        M: Copy + Monoid + Act<X>
+    {
+        self.path_intervals(u, v, edge).iter().fold(X::one(), |acc, &(l, r, rev)| {
+            acc.ope(&if rev { seg.rseg.fold(l, r).0 } else { seg.seg.fold(l, r) })
+        })
+    }
+    // act m on the path
+    pub fn path_update<X, M>(&self, seg: &mut PathLazySegTree<X, M>, u: usize, v: usize, m: M, edge: bool)
+    where
+        X: Copy + Monoid,
+        M: Copy + Monoid + Act<X>
+    {
+        for (l, r, _) in self.path_intervals(u, v, edge) {
+            seg.range_update(l, r, m);
         }
-        pub fn root(&self) -> usize { self.root }
-        pub fn len(&self) -> usize { self.n }
-        pub fn parent(&self, v: usize) -> Option<usize> { self.parents[v] }
-        pub fn children(&self, v: usize) -> std::slice::Iter<usize> { self.children[v].iter() }
     }
 }
 
-pub mod hld {
-    use crate::directed_tree::*;
+// the values in seq order for path_fold, held forward and backward
+// so that the paths walked toward the root fold in order even if X is not commutative
+pub struct PathSegTree<X> {
+    seg: SegTree<X>,
+    rseg: SegTree<Rev<X>>
+}
 
-    pub struct HLD {
-        pub seq: Vec<usize>,
-        pub tree: DirectedTreeNorm,
-        pub subtree_size: Vec<usize>,
-        pub heavy: Vec<Option<usize>>,
-        pub seq_head: Vec<usize>,
-        pub t_in: Vec<usize>,
-        pub t_out: Vec<usize>
+impl<X: Copy + Monoid> PathSegTree<X> {
+    pub fn from(xs: Vec<X>) -> Self {
+        let rseg = SegTree::from(xs.iter().map(|&x| Rev(x)).collect());
+        PathSegTree { seg: SegTree::from(xs), rseg }
+    }
+    pub fn update(&mut self, i: usize, x: X) {
+        self.seg.update(i, x);
+        self.rseg.update(i, Rev(x));
     }
+    // fold of [l, r) of seq, such as subtree_interval
+    pub fn fold(&self, l: usize, r: usize) -> X {
+        self.seg.fold(l, r)
+    }
+}
 
-    impl HLD {
-        pub fn new(root: usize, n: usize, E: &Vec<(usize, usize)>) -> Self {
-            let tree = DirectedTreeNorm::new(root, n, E);
-            HLD::from(&tree)
-        }
-        pub fn from(tree: &DirectedTreeNorm) -> Self {
-            let mut hld = HLD {
-                seq: vec![],
-                tree: tree.clone(),
-                subtree_size: vec![0; tree.len()],
-                heavy: vec![None; tree.len()],
-                seq_head: vec![!0; tree.len()],
-                t_in: vec![0; tree.len()],
-                t_out: vec![0; tree.len()]
-            };
-            hld._dfs_size(tree, tree.root());
-            hld.seq_head[tree.root()] = tree.root();
-            hld._dfs_hld(tree, tree.root(), &mut 0);
-            hld
-        }
-        fn _dfs_size(&mut self, tree: &DirectedTreeNorm, u: usize) {
-            self.subtree_size[u] = 1;
-            for &v in tree.children(u) {
-                self._dfs_size(tree, v);
-                self.subtree_size[u] += self.subtree_size[v];
-            }
-            self.heavy[u] = tree.children(u).max_by_key(|&&i| self.subtree_size[i]).map(|&x| x);
-        }
-        fn _dfs_hld(&mut self, tree: &DirectedTreeNorm, u: usize, t: &mut usize) {
-            self.t_in[u] = *t;
-            self.seq.push(u);
-            *t += 1;
-            if let Some(h) = self.heavy[u] {
-                self.seq_head[h] = self.seq_head[u];
-                self._dfs_hld(tree, h, t);
-                for &v in tree.children(u).filter(|&&v| v != h) {
-                    self.seq_head[v] = v;
-                    self._dfs_hld(tree, v, t);
-                }
-            }
-            self.t_out[u] = *t;
-        }
-        pub fn sequence(&self) -> std::slice::Iter<usize> { self.seq.iter() }
-        pub fn lca(&self, u: usize, v: usize) -> usize {
-            let (mut u, mut v) = (u, v);
-            while u != v {
-                if self.t_in[u] > self.t_in[v] { std::mem::swap(&mut u, &mut v); }
-                if self.seq_head[u] == self.seq_head[v] { break; }
-                v = self.tree.parent(self.seq_head[v]).unwrap();
-            }
-            u
-        }
+// PathSegTree with the actions, for path_fold_lazy and path_update
+pub struct PathLazySegTree<X, M> {
+    seg: LazySegTree<X, M>,
+    rseg: LazySegTree<Rev<X>, RevAct<M>>
+}
+
+impl<X: Copy + Monoid, M: Copy + Monoid + Act<X>> PathLazySegTree<X, M> {
+    pub fn from(xs: Vec<X>) -> Self {
+        let mut seg = LazySegTree::new(xs.len());
+        let mut rseg = LazySegTree::new(xs.len());
+        rseg.build(xs.iter().map(|&x| Rev(x)).collect());
+        seg.build(xs);
+        PathLazySegTree { seg, rseg }
+    }
+    pub fn set_val(&mut self, i: usize, x: X) {
+        self.seg.set_val(i, x);
+        self.rseg.set_val(i, Rev(x));
+    }
+    // act m on [l, r) of seq, such as subtree_interval
+    pub fn range_update(&mut self, l: usize, r: usize, m: M) {
+        self.seg.range_update(l, r, m);
+        self.rseg.range_update(l, r, RevAct(m));
+    }
+    pub fn fold(&mut self, l: usize, r: usize) -> X {
+        self.seg.fold(l, r)
+    }
+    // the values in seq order
+    pub fn iter(&mut self) -> std::slice::Iter<'_, X> {
+        self.seg.iter()
     }
 }
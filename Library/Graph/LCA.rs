@@ -1,3 +1,5 @@
+use crate::graph::{Adjacency, Graph};
+
 pub struct LCA {
     depth: Vec<usize>,
     table: Vec<Vec<usize>>,
@@ -5,19 +7,28 @@ pub struct LCA {
 
 impl LCA {
     pub fn new(N: usize, root: usize, graph: Vec<Vec<usize>>, MAX: usize) -> Self {
+        assert!(graph.len() == N);
+        LCA::from_graph(&Graph::from_lists(&graph), root, MAX)
+    }
+    // G has both directions of every edge, and the weights are dropped
+    pub fn from_graph<W>(G: &impl Adjacency<W>, root: usize, MAX: usize) -> Self {
+        let N = G.len();
         let mut par = vec![root; N];
         let mut depth = vec![0; N];
-        LCA::_dfs(root, &graph, &mut par, &mut depth);
+        LCA::_bfs(root, G, &mut par, &mut depth);
         let mut table = vec![vec![0; N]; MAX];
         LCA::_doubling(&par, &mut table);
         LCA { depth, table }
     }
-    fn _dfs(u: usize, G: &Vec<Vec<usize>>, par: &mut Vec<usize>, depth: &mut Vec<usize>) {
-        for &v in &G[u] {
-            if v == par[u] { continue; }
-            par[v] = u;
-            depth[v] = depth[u] + 1;
-            LCA::_dfs(v, G, par, depth);
+    fn _bfs<W>(root: usize, G: &impl Adjacency<W>, par: &mut Vec<usize>, depth: &mut Vec<usize>) {
+        let mut queue = std::collections::VecDeque::from([root]);
+        while let Some(u) = queue.pop_front() {
+            for &(v, _) in G.neighbors(u) {
+                if v == par[u] { continue; }
+                par[v] = u;
+                depth[v] = depth[u] + 1;
+                queue.push_back(v);
+            }
         }
     }
     fn _doubling(par: &Vec<usize>, table: &mut Vec<Vec<usize>>) {
@@ -0,0 +1,232 @@
+// link cut tree over a forest, with path fold and path update
+// X must be able to fold in both directions since evert reverses paths,
+// and M acts on the folded value as in LazySegTree
+// amortized O(log n) per operation
+
+use crate::algebra::*;
+
+const NIL: usize = usize::MAX;
+
+#[derive(Clone)]
+pub struct LinkCutTree<X, M> {
+    ch: Vec<[usize; 2]>,
+    // the parent in the splay tree, or the path parent if this is the root of a splay tree
+    par: Vec<usize>,
+    val: Vec<X>,
+    // fold of the splay subtree from left to right, and from right to left
+    sum: Vec<X>,
+    rsum: Vec<X>,
+    lazy: Vec<M>,
+    rev: Vec<bool>
+}
+
+impl<X: Copy + Monoid, M: Copy + Monoid + Act<X>> LinkCutTree<X, M> {
+    pub fn new(n: usize) -> Self {
+        Self::from(vec![X::one(); n])
+    }
+
+    pub fn from(xs: Vec<X>) -> Self {
+        let n = xs.len();
+        LinkCutTree {
+            ch: vec![[NIL, NIL]; n],
+            par: vec![NIL; n],
+            val: xs.clone(),
+            sum: xs.clone(),
+            rsum: xs,
+            lazy: vec![M::one(); n],
+            rev: vec![false; n]
+        }
+    }
+
+    fn _is_root(&self, x: usize) -> bool {
+        let p = self.par[x];
+        p == NIL || (self.ch[p][0] != x && self.ch[p][1] != x)
+    }
+
+    fn _apply(&mut self, x: usize, m: M) {
+        if x == NIL { return; }
+        self.val[x] = m.act(self.val[x]);
+        self.sum[x] = m.act(self.sum[x]);
+        self.rsum[x] = m.act(self.rsum[x]);
+        self.lazy[x] = self.lazy[x].ope(&m);
+    }
+
+    fn _toggle(&mut self, x: usize) {
+        if x == NIL { return; }
+        self.ch[x].swap(0, 1);
+        std::mem::swap(&mut self.sum[x], &mut self.rsum[x]);
+        self.rev[x] ^= true;
+    }
+
+    fn _push(&mut self, x: usize) {
+        let [l, r] = self.ch[x];
+        let m = std::mem::replace(&mut self.lazy[x], M::one());
+        self._apply(l, m);
+        self._apply(r, m);
+        if self.rev[x] {
+            self._toggle(l);
+            self._toggle(r);
+            self.rev[x] = false;
+        }
+    }
+
+    fn _recalc(&mut self, x: usize) {
+        let [l, r] = self.ch[x];
+        let (mut s, mut rs) = (self.val[x], self.val[x]);
+        if l != NIL {
+            s = self.sum[l].ope(&s);
+            rs = rs.ope(&self.rsum[l]);
+        }
+        if r != NIL {
+            s = s.ope(&self.sum[r]);
+            rs = self.rsum[r].ope(&rs);
+        }
+        self.sum[x] = s;
+        self.rsum[x] = rs;
+    }
+
+    fn _rotate(&mut self, x: usize) {
+        let p = self.par[x];
+        let g = self.par[p];
+        let d = (self.ch[p][1] == x) as usize;
+        let c = self.ch[x][d ^ 1];
+        if !self._is_root(p) {
+            let pd = (self.ch[g][1] == p) as usize;
+            self.ch[g][pd] = x;
+        }
+        self.par[x] = g;
+        self.ch[x][d ^ 1] = p;
+        self.par[p] = x;
+        self.ch[p][d] = c;
+        if c != NIL { self.par[c] = p; }
+        self._recalc(p);
+        self._recalc(x);
+    }
+
+    fn _splay(&mut self, x: usize) {
+        // push the tags from the root of the splay tree down to x
+        let mut path = vec![x];
+        while !self._is_root(*path.last().unwrap()) {
+            path.push(self.par[*path.last().unwrap()]);
+        }
+        for &y in path.iter().rev() {
+            self._push(y);
+        }
+        while !self._is_root(x) {
+            let p = self.par[x];
+            if !self._is_root(p) {
+                let g = self.par[p];
+                if (self.ch[g][1] == p) == (self.ch[p][1] == x) {
+                    self._rotate(p);
+                } else {
+                    self._rotate(x);
+                }
+            }
+            self._rotate(x);
+        }
+    }
+
+    // make the path from the root to x preferred, and returns the last node switched to
+    fn _access(&mut self, x: usize) -> usize {
+        let mut last = NIL;
+        let mut y = x;
+        while y != NIL {
+            self._splay(y);
+            self.ch[y][1] = last;
+            self._recalc(y);
+            last = y;
+            y = self.par[y];
+        }
+        self._splay(x);
+        last
+    }
+
+    // make v the root of its tree
+    pub fn evert(&mut self, v: usize) {
+        self._access(v);
+        self._toggle(v);
+        self._push(v);
+    }
+
+    // the root of the tree containing v
+    pub fn root(&mut self, v: usize) -> usize {
+        self._access(v);
+        let mut x = v;
+        loop {
+            self._push(x);
+            if self.ch[x][0] == NIL { break; }
+            x = self.ch[x][0];
+        }
+        self._splay(x);
+        x
+    }
+
+    pub fn connected(&mut self, u: usize, v: usize) -> bool {
+        self.root(u) == self.root(v)
+    }
+
+    // add the edge (u, v), where u and v are in different trees
+    pub fn link(&mut self, u: usize, v: usize) {
+        assert!(!self.connected(u, v), "ALREADY CONNECTED");
+        self.evert(u);
+        self.par[u] = v;
+    }
+
+    // remove the edge (u, v), which must exist
+    pub fn cut(&mut self, u: usize, v: usize) {
+        self.evert(u);
+        self._access(v);
+        assert!(self.ch[v][0] == u && self.ch[u][1] == NIL, "NO SUCH EDGE");
+        self.ch[v][0] = NIL;
+        self.par[u] = NIL;
+        self._recalc(v);
+    }
+
+    // the lca under the current root, or None if not connected
+    pub fn lca(&mut self, u: usize, v: usize) -> Option<usize> {
+        if !self.connected(u, v) { return None; }
+        self._access(u);
+        Some(self._access(v))
+    }
+
+    // the parent under the current root
+    pub fn parent(&mut self, v: usize) -> Option<usize> {
+        self._access(v);
+        let mut x = self.ch[v][0];
+        if x == NIL { return None; }
+        loop {
+            self._push(x);
+            if self.ch[x][1] == NIL { break; }
+            x = self.ch[x][1];
+        }
+        self._splay(x);
+        Some(x)
+    }
+
+    pub fn get(&mut self, v: usize) -> X {
+        self._access(v);
+        self.val[v]
+    }
+
+    pub fn set(&mut self, v: usize, x: X) {
+        self._access(v);
+        self.val[v] = x;
+        self._recalc(v);
+    }
+
+    // fold of the vertices on the path from u to v, in this order
+    pub fn path_fold(&mut self, u: usize, v: usize) -> X {
+        assert!(self.connected(u, v), "NOT CONNECTED");
+        self.evert(u);
+        self._access(v);
+        self.sum[v]
+    }
+
+    // act m on every vertex on the path between u and v
+    pub fn path_apply(&mut self, u: usize, v: usize, m: M) {
+        assert!(self.connected(u, v), "NOT CONNECTED");
+        self.evert(u);
+        self._access(v);
+        self._apply(v, m);
+    }
+}
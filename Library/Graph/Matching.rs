@@ -0,0 +1,289 @@
+// matchings: Hopcroft-Karp for bipartite graphs with a König vertex cover,
+// the Hungarian algorithm for assignment problems, and Edmonds' blossom algorithm for general graphs
+
+use crate::graph::Adjacency;
+
+const NIL: usize = usize::MAX;
+
+// the left vertices 0..l and the right vertices 0..r
+// O(m sqrt(n))
+#[derive(Clone)]
+pub struct BipartiteMatching {
+    adj: Vec<Vec<usize>>,
+    mate_l: Vec<usize>,
+    mate_r: Vec<usize>
+}
+
+impl BipartiteMatching {
+    pub fn new(l: usize, r: usize) -> Self {
+        BipartiteMatching { adj: vec![vec![]; l], mate_l: vec![NIL; l], mate_r: vec![NIL; r] }
+    }
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        assert!(u < self.mate_l.len() && v < self.mate_r.len(), "VERTICES NUMBER PROCEEDING");
+        self.adj[u].push(v);
+    }
+    // the size of a maximum matching
+    pub fn solve(&mut self) -> usize {
+        let l = self.mate_l.len();
+        loop {
+            // the layers of the left vertices from the unmatched ones
+            let mut dist = vec![NIL; l];
+            let mut queue = std::collections::VecDeque::new();
+            for u in 0..l {
+                if self.mate_l[u] == NIL {
+                    dist[u] = 0;
+                    queue.push_back(u);
+                }
+            }
+            // the first layer next to an unmatched right vertex, where the shortest augmenting paths end
+            let mut limit = NIL;
+            while let Some(u) = queue.pop_front() {
+                if dist[u] > limit { break; }
+                for &v in &self.adj[u] {
+                    let w = self.mate_r[v];
+                    if w == NIL {
+                        limit = dist[u];
+                    } else if dist[w] == NIL && dist[u] < limit {
+                        dist[w] = dist[u] + 1;
+                        queue.push_back(w);
+                    }
+                }
+            }
+            if limit == NIL { break; }
+            let mut it = vec![0; l];
+            for u in 0..l {
+                if self.mate_l[u] == NIL {
+                    self._augment(u, limit, &mut dist, &mut it);
+                }
+            }
+        }
+        self.mate_l.iter().filter(|&&v| v != NIL).count()
+    }
+    // a shortest augmenting path along the layers up to limit on an explicit stack of the left vertices
+    fn _augment(&mut self, root: usize, limit: usize, dist: &mut Vec<usize>, it: &mut Vec<usize>) -> bool {
+        let mut stack = vec![root];
+        while let Some(&u) = stack.last() {
+            if let Some(&v) = self.adj[u].get(it[u]) {
+                let w = self.mate_r[v];
+                if w == NIL && dist[u] == limit {
+                    // flip the path, where it[x] points to the edge taken from each x on the stack
+                    for &x in stack.iter().rev() {
+                        let y = self.adj[x][it[x]];
+                        self.mate_r[y] = x;
+                        self.mate_l[x] = y;
+                    }
+                    return true;
+                }
+                if w != NIL && dist[u] < limit && dist[w] == dist[u] + 1 {
+                    stack.push(w);
+                } else {
+                    it[u] += 1;
+                }
+            } else {
+                // a dead end is never visited again in this phase
+                dist[u] = NIL;
+                stack.pop();
+                if let Some(&p) = stack.last() {
+                    it[p] += 1;
+                }
+            }
+        }
+        false
+    }
+    // the pairs (u, v) of the matching
+    pub fn pairs(&self) -> Vec<(usize, usize)> {
+        (0..self.mate_l.len()).filter(|&u| self.mate_l[u] != NIL).map(|u| (u, self.mate_l[u])).collect()
+    }
+    // a minimum vertex cover as (left, right) after solve, the same size as the maximum matching
+    // the left vertices unreachable and the right vertices reachable from the unmatched left vertices
+    // by the alternating paths
+    pub fn vertex_cover(&self) -> (Vec<bool>, Vec<bool>) {
+        let (l, r) = (self.mate_l.len(), self.mate_r.len());
+        let mut left = vec![true; l];
+        let mut right = vec![false; r];
+        let mut stack: Vec<usize> = (0..l).filter(|&u| self.mate_l[u] == NIL).collect();
+        for &u in &stack {
+            left[u] = false;
+        }
+        while let Some(u) = stack.pop() {
+            for &v in &self.adj[u] {
+                if right[v] { continue; }
+                right[v] = true;
+                let w = self.mate_r[v];
+                if w != NIL && left[w] {
+                    left[w] = false;
+                    stack.push(w);
+                }
+            }
+        }
+        (left, right)
+    }
+}
+
+pub trait AssignmentCost:
+    Copy + PartialOrd
+    + std::ops::Add<Output = Self>
+    + std::ops::Sub<Output = Self>
+    + std::fmt::Debug
+{
+    fn zero() -> Self;
+    fn inf() -> Self;
+}
+
+impl AssignmentCost for i64 {
+    fn zero() -> Self { 0 }
+    fn inf() -> Self { i64::MAX / 4 }
+}
+
+impl AssignmentCost for f64 {
+    fn zero() -> Self { 0.0 }
+    fn inf() -> Self { f64::INFINITY }
+}
+
+// the minimum total cost to assign each row to a distinct column, and the column of each row,
+// where the number of rows is at most the number of columns
+// O(n^2 m)
+pub fn hungarian<T: AssignmentCost>(a: &Vec<Vec<T>>) -> (T, Vec<usize>) {
+    let n = a.len();
+    let m = if n == 0 { 0 } else { a[0].len() };
+    assert!(n <= m, "MORE ROWS THAN COLUMNS");
+    // the potentials of the rows and the columns, and the row of each column, with the column m as the sentinel
+    let mut u = vec![T::zero(); n + 1];
+    let mut v = vec![T::zero(); m + 1];
+    let mut row = vec![NIL; m + 1];
+    let mut way = vec![m; m + 1];
+    for i in 0..n {
+        row[m] = i;
+        let mut j0 = m;
+        let mut minv = vec![T::inf(); m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = row[j0];
+            let mut delta = T::inf();
+            let mut j1 = m;
+            for j in 0..m {
+                if used[j] { continue; }
+                let cur = a[i0][j] - u[i0] - v[j];
+                if cur < minv[j] {
+                    minv[j] = cur;
+                    way[j] = j0;
+                }
+                if minv[j] < delta {
+                    delta = minv[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[row[j]] = u[row[j]] + delta;
+                    v[j] = v[j] - delta;
+                } else {
+                    minv[j] = minv[j] - delta;
+                }
+            }
+            j0 = j1;
+            if row[j0] == NIL { break; }
+        }
+        // flip the alternating path back to the sentinel
+        while j0 != m {
+            let j1 = way[j0];
+            row[j0] = row[j1];
+            j0 = j1;
+        }
+    }
+    let mut assign = vec![0; n];
+    for j in 0..m {
+        if row[j] != NIL {
+            assign[row[j]] = j;
+        }
+    }
+    let cost = (0..n).fold(T::zero(), |acc, i| acc + a[i][assign[i]]);
+    (cost, assign)
+}
+
+// a maximum matching of a general graph as the mate of each vertex, by Edmonds' blossom algorithm
+// G has both directions of every edge, and the weights are dropped
+// O(n^3)
+pub fn max_matching<W>(G: &impl Adjacency<W>) -> Vec<Option<usize>> {
+    let n = G.len();
+    let mut mate = vec![NIL; n];
+    for root in 0..n {
+        if mate[root] != NIL { continue; }
+        if let Some((mut v, p)) = _find_path(G, root, &mate) {
+            // flip the augmenting path back to the root
+            while v != NIL {
+                let pv = p[v];
+                let next = mate[pv];
+                mate[v] = pv;
+                mate[pv] = v;
+                v = next;
+            }
+        }
+    }
+    mate.into_iter().map(|v| if v == NIL { None } else { Some(v) }).collect()
+}
+
+// an unmatched vertex at the end of an augmenting path from root, and the parents on the alternating tree
+// base[v] is the base of the blossom containing v
+fn _find_path<W>(G: &impl Adjacency<W>, root: usize, mate: &[usize]) -> Option<(usize, Vec<usize>)> {
+    let n = G.len();
+    let mut used = vec![false; n];
+    let mut p = vec![NIL; n];
+    let mut base: Vec<usize> = (0..n).collect();
+    used[root] = true;
+    let mut queue = std::collections::VecDeque::from([root]);
+    while let Some(v) = queue.pop_front() {
+        for &(to, _) in G.neighbors(v) {
+            if base[v] == base[to] || mate[v] == to { continue; }
+            if to == root || (mate[to] != NIL && p[mate[to]] != NIL) {
+                // an odd cycle, which is contracted into its base
+                let cur = _lca(v, to, &base, mate, &p);
+                let mut blossom = vec![false; n];
+                _mark_path(v, cur, to, &base, mate, &mut p, &mut blossom);
+                _mark_path(to, cur, v, &base, mate, &mut p, &mut blossom);
+                for i in 0..n {
+                    if blossom[base[i]] {
+                        base[i] = cur;
+                        if !used[i] {
+                            used[i] = true;
+                            queue.push_back(i);
+                        }
+                    }
+                }
+            } else if p[to] == NIL {
+                p[to] = v;
+                if mate[to] == NIL { return Some((to, p)); }
+                used[mate[to]] = true;
+                queue.push_back(mate[to]);
+            }
+        }
+    }
+    None
+}
+
+// the base of the blossom where the paths from a and b to the root meet
+fn _lca(mut a: usize, mut b: usize, base: &[usize], mate: &[usize], p: &[usize]) -> usize {
+    let mut used = vec![false; base.len()];
+    loop {
+        a = base[a];
+        used[a] = true;
+        if mate[a] == NIL { break; }
+        a = p[mate[a]];
+    }
+    loop {
+        b = base[b];
+        if used[b] { return b; }
+        b = p[mate[b]];
+    }
+}
+
+fn _mark_path(mut v: usize, b: usize, mut child: usize, base: &[usize], mate: &[usize], p: &mut [usize], blossom: &mut [bool]) {
+    while base[v] != b {
+        blossom[base[v]] = true;
+        blossom[base[mate[v]]] = true;
+        p[v] = child;
+        child = mate[v];
+        v = p[mate[v]];
+    }
+}
@@ -0,0 +1,36 @@
+// transitive closure with a bitset of the reachable groups for each strongly connected group
+// O(n + m + n' m' / 64) time and n'^2 / 8 bytes for n' groups and m' edges between them,
+// e.g. about 300 MB for a DAG of 5e4 vertices
+
+use fixedbitset::FixedBitSet;
+use crate::graph::Adjacency;
+use crate::graph::scc::SCC;
+
+pub struct Reachability {
+    cmp: Vec<usize>,
+    reach: Vec<FixedBitSet>
+}
+
+impl Reachability {
+    pub fn from_graph<W>(G: &impl Adjacency<W>) -> Self {
+        let mut scc = SCC::from_graph(G);
+        let cmp = scc.cmp().clone();
+        let dag = scc.dag();
+        let num = dag.len();
+        let mut reach = vec![FixedBitSet::with_capacity(num); num];
+        // the groups are in topological order, so the later ones are done first
+        for c in (0..num).rev() {
+            let mut bits = std::mem::take(&mut reach[c]);
+            bits.insert(c);
+            for &d in &dag[c] {
+                bits.union_with(&reach[d]);
+            }
+            reach[c] = bits;
+        }
+        Reachability { cmp, reach }
+    }
+    // whether there is a path from u to v, where u reaches itself
+    pub fn reachable(&self, u: usize, v: usize) -> bool {
+        self.reach[self.cmp[u]].contains(self.cmp[v])
+    }
+}
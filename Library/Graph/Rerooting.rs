@@ -0,0 +1,52 @@
+// rerooting dp: the dp value with every vertex as the root in O(n), without recursion
+// X: the merge of the children, which should be commutative
+// put_edge(x, w): the dp value x of a child through the edge of weight w
+// put_vertex(x, v): the merged children x finished at v
+
+use crate::algebra::Monoid;
+use crate::graph::directed_tree::DirectedTree;
+
+pub fn rerooting<T, X>(
+    tree: &DirectedTree<T>,
+    put_edge: impl Fn(X, T) -> X,
+    put_vertex: impl Fn(X, usize) -> X,
+) -> Vec<X>
+where
+    T: Copy,
+    X: Copy + Monoid,
+{
+    let n = tree.len();
+    // bfs order, so that parents come before children
+    let mut order = vec![tree.root()];
+    for i in 0..n {
+        let v = order[i];
+        order.extend(tree.children(v).map(|&(c, _)| c));
+    }
+
+    // down[v]: the dp value of the subtree of v
+    let mut down = vec![X::one(); n];
+    for &v in order.iter().rev() {
+        let acc = tree.children(v).fold(X::one(), |acc, &(c, w)| acc.ope(&put_edge(down[c], w)));
+        down[v] = put_vertex(acc, v);
+    }
+
+    // up[v]: the contribution of the side of the parent to v, through the edge
+    let mut up = vec![X::one(); n];
+    let mut ans = vec![X::one(); n];
+    for &v in &order {
+        let ch: Vec<(usize, T)> = tree.children(v).copied().collect();
+        let k = ch.len();
+        // suffix[i]: merge of the children i..k
+        let mut suffix = vec![X::one(); k + 1];
+        for i in (0..k).rev() {
+            suffix[i] = put_edge(down[ch[i].0], ch[i].1).ope(&suffix[i + 1]);
+        }
+        ans[v] = put_vertex(up[v].ope(&suffix[0]), v);
+        let mut prefix = up[v];
+        for (i, &(c, w)) in ch.iter().enumerate() {
+            up[c] = put_edge(put_vertex(prefix.ope(&suffix[i + 1]), v), w);
+            prefix = prefix.ope(&put_edge(down[c], w));
+        }
+    }
+    ans
+}
@@ -1,110 +1,140 @@
-pub mod scc {
-    // nord i belongs to the group cmp[i]
-    // dag is the adjective list of cmp
+// nord i belongs to the group cmp[i]
+// dag is the adjective list of cmp, sorted and without duplicate edges
+// the groups are in topological order: cmp[u] <= cmp[v] for every edge u -> v
 
-    pub struct SCC {
-        N: usize,
-        E: Vec<(usize, usize)>,
-        is_calculated: bool,
-        cmp: Vec<usize>,
-        dag: Vec<Vec<usize>>
-    }
+use crate::graph::{Adjacency, CsrGraph, Graph};
 
-    impl SCC {
-        pub fn new(N: usize) -> Self {
-            SCC { N, E: vec![], is_calculated: false, cmp: vec![], dag: vec![] }
-        }
-        #[inline]
-        pub fn add_edge(&mut self, u: usize, v: usize) {
-            self.E.push((u, v));
-            self.is_calculated = false;
-        }
-        pub fn calculate(&mut self) {
-            let mut G = vec![vec![]; self.N];
-            let mut G_rev = vec![vec![]; self.N];
-            for &(u, v) in &self.E {
-                G[u].push(v);
+pub struct SCC {
+    N: usize,
+    E: Vec<(usize, usize)>,
+    is_calculated: bool,
+    cmp: Vec<usize>,
+    dag: Vec<Vec<usize>>
+}
+
+impl SCC {
+    pub fn new(N: usize) -> Self {
+        SCC { N, E: vec![], is_calculated: false, cmp: vec![], dag: vec![] }
+    }
+    #[inline]
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        self.E.push((u, v));
+        self.is_calculated = false;
+    }
+    pub fn calculate(&mut self) {
+        let E: Vec<_> = self.E.iter().map(|&(u, v)| (u, v, ())).collect();
+        self._calculate(&CsrGraph::from_edges(self.N, &E));
+    }
+    fn _calculate<W>(&mut self, G: &impl Adjacency<W>) {
+        let mut G_rev = vec![vec![]; self.N];
+        for u in 0..self.N {
+            for &(v, _) in G.neighbors(u) {
                 G_rev[v].push(u);
             }
-            let mut idx = vec![];
-            let mut searched = vec![false; self.N];
-            for u in 0..self.N {
-                if !searched[u] {
-                    self._dfs(u, &G, &mut searched, &mut idx);
-                }
+        }
+        let mut idx = vec![];
+        let mut searched = vec![false; self.N];
+        for u in 0..self.N {
+            if !searched[u] {
+                self._dfs(u, G, &mut searched, &mut idx);
             }
-            searched = vec![false; self.N];
-            self.cmp = vec![self.N; self.N];
-            let mut num = 0;
-            for &u in idx.iter().rev() {
-                if !searched[u] {
-                    self._rdfs(u, &G_rev, &mut searched, num);
-                    num += 1;
-                }
+        }
+        searched = vec![false; self.N];
+        self.cmp = vec![self.N; self.N];
+        let mut num = 0;
+        for &u in idx.iter().rev() {
+            if !searched[u] {
+                self._rdfs(u, &G_rev, &mut searched, num);
+                num += 1;
             }
-            self.dag = vec![vec![]; num];
-            for u in 0..self.N {
-                let u2 = self.cmp[u];
-                for &v in &G[u] {
-                    let v2 = self.cmp[v];
-                    if u2 != v2 {
-                        self.dag[u2].push(v2);
-                    }
+        }
+        self.dag = vec![vec![]; num];
+        for u in 0..self.N {
+            let u2 = self.cmp[u];
+            for &(v, _) in G.neighbors(u) {
+                let v2 = self.cmp[v];
+                if u2 != v2 {
+                    self.dag[u2].push(v2);
                 }
             }
-            for u in 0..num {
-                self.dag[u].sort();
-                self.dag[u].dedup();
-            }
-            self.is_calculated = true;
         }
-        pub fn from(N: usize, E: Vec<(usize, usize)>) -> Self {
-            let mut scc = SCC::new(N);
-            scc.E = E;
-            scc.calculate();
-            scc
+        for u in 0..num {
+            self.dag[u].sort();
+            self.dag[u].dedup();
         }
-        fn _dfs(
-            &self,
-            u: usize,
-            G: &Vec<Vec<usize>>,
-            searched: &mut Vec<bool>,
-            idx: &mut Vec<usize>
-        ) {
-            searched[u] = true;
-            for &v in &G[u] {
+        self.is_calculated = true;
+    }
+    pub fn from(N: usize, E: Vec<(usize, usize)>) -> Self {
+        let mut scc = SCC::new(N);
+        scc.E = E;
+        scc.calculate();
+        scc
+    }
+    // the weights are dropped, and the edges are kept so that add_edge extends G
+    pub fn from_graph<W>(G: &impl Adjacency<W>) -> Self {
+        let mut scc = SCC::new(G.len());
+        scc.E = (0..G.len()).flat_map(|u| G.neighbors(u).iter().map(move |&(v, _)| (u, v))).collect();
+        scc._calculate(G);
+        scc
+    }
+    // post order on an explicit stack of (vertex, the next index in G[vertex])
+    fn _dfs<W>(
+        &self,
+        root: usize,
+        G: &impl Adjacency<W>,
+        searched: &mut Vec<bool>,
+        idx: &mut Vec<usize>
+    ) {
+        searched[root] = true;
+        let mut stack = vec![(root, 0)];
+        while let Some((u, i)) = stack.last_mut() {
+            let u = *u;
+            if let Some(&(v, _)) = G.neighbors(u).get(*i) {
+                *i += 1;
                 if searched[v] { continue; }
-                self._dfs(v, G, searched, idx);
+                searched[v] = true;
+                stack.push((v, 0));
+            } else {
+                stack.pop();
+                idx.push(u);
             }
-            idx.push(u);
         }
-        fn _rdfs(
-            &mut self,
-            u: usize,
-            G_rev: &Vec<Vec<usize>>,
-            searched: &mut Vec<bool>,
-            num: usize,
-        ) {
+    }
+    // the component is all that matters, so the order of the visit is free
+    fn _rdfs(
+        &mut self,
+        root: usize,
+        G_rev: &Vec<Vec<usize>>,
+        searched: &mut Vec<bool>,
+        num: usize,
+    ) {
+        searched[root] = true;
+        let mut stack = vec![root];
+        while let Some(u) = stack.pop() {
             self.cmp[u] = num;
-            searched[u] = true;
             for &v in &G_rev[u] {
                 if searched[v] { continue; }
-                self._rdfs(v, G_rev, searched, num);
+                searched[v] = true;
+                stack.push(v);
             }
         }
-        #[inline]
-        pub fn cmp(&mut self) -> &Vec<usize> {
-            if !self.is_calculated {
-                self.calculate()
-            }
-            &self.cmp
+    }
+    #[inline]
+    pub fn cmp(&mut self) -> &Vec<usize> {
+        if !self.is_calculated {
+            self.calculate()
         }
-        #[inline]
-        pub fn dag(&mut self) -> &Vec<Vec<usize>> {
-            if !self.is_calculated {
-                self.calculate()
-            }
-            &self.dag
+        &self.cmp
+    }
+    #[inline]
+    pub fn dag(&mut self) -> &Vec<Vec<usize>> {
+        if !self.is_calculated {
+            self.calculate()
         }
+        &self.dag
+    }
+    // the condensation of the graph, whose vertices are the groups
+    pub fn condensation(&mut self) -> Graph<()> {
+        Graph::from_lists(self.dag())
     }
 }
@@ -1,36 +1,180 @@
-fn dijkstra(N: usize, G: &Vec<Vec<usize>>, st: usize) -> Vec<usize> {
-    let INF = 1 << 60;
-    let mut dist = vec![INF; N + 1];
-    dist[st] = 0;
+use crate::graph::{Adjacency, Weight};
+
+// dist[v] is W::inf() if v is unreachable, and prev[v] is the vertex before v on a shortest path
+#[derive(Debug, Clone)]
+pub struct ShortestPath<W> {
+    pub dist: Vec<W>,
+    pub prev: Vec<Option<usize>>
+}
+
+impl<W: Weight> ShortestPath<W> {
+    fn _new(n: usize, st: usize) -> Self {
+        let mut dist = vec![W::inf(); n];
+        dist[st] = W::zero();
+        ShortestPath { dist, prev: vec![None; n] }
+    }
+
+    // the vertices on a shortest path from the start to t, or None if unreachable
+    pub fn path_to(&self, t: usize) -> Option<Vec<usize>> {
+        if self.dist[t] == W::inf() { return None; }
+        let mut path = vec![t];
+        while let Some(u) = self.prev[*path.last().unwrap()] {
+            path.push(u);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// the weights must be nonnegative
+// O((n + m) log m)
+pub fn dijkstra<W: Weight>(G: &impl Adjacency<W>, st: usize) -> ShortestPath<W> {
+    let mut sp = ShortestPath::_new(G.len(), st);
     let mut hq = std::collections::BinaryHeap::new();
-    hq.push((std::cmp::Reverse(0), st));
+    hq.push((std::cmp::Reverse(W::zero()), st));
     while let Some((std::cmp::Reverse(d), p)) = hq.pop() {
-        if d > dist[p] { continue; }
-        for &q in &G[p] {
-            if dist[q] > d + 1 {
-                dist[q] = d + 1;
-                hq.push((std::cmp::Reverse(dist[q]), q));
+        if d > sp.dist[p] { continue; }
+        for &(q, w) in G.neighbors(p) {
+            assert!(w >= W::zero(), "NEGATIVE WEIGHT");
+            if sp.dist[q] > d + w {
+                sp.dist[q] = d + w;
+                sp.prev[q] = Some(p);
+                hq.push((std::cmp::Reverse(sp.dist[q]), q));
             }
         }
     }
-    return dist;
+    sp
 }
 
-fn warshall_floyd(N: usize, G: &Vec<Vec<(usize, usize)>>) -> Vec<Vec<usize>> {
-    let INF = 1 << 60;
+// the weights must be 0 or a common positive value, e.g. 0 and 1
+// O(n + m)
+pub fn bfs01<W: Weight>(G: &impl Adjacency<W>, st: usize) -> ShortestPath<W> {
+    let mut sp = ShortestPath::_new(G.len(), st);
+    let mut done = vec![false; G.len()];
+    let mut dq = std::collections::VecDeque::from([st]);
+    while let Some(p) = dq.pop_front() {
+        if done[p] { continue; }
+        done[p] = true;
+        for &(q, w) in G.neighbors(p) {
+            if sp.dist[q] > sp.dist[p] + w {
+                sp.dist[q] = sp.dist[p] + w;
+                sp.prev[q] = Some(p);
+                if w == W::zero() { dq.push_front(q); } else { dq.push_back(q); }
+            }
+        }
+    }
+    sp
+}
+
+// negative[v] is true if a negative cycle lies on some path from the start to v,
+// and then dist[v] and prev[v] are meaningless
+// O(nm)
+pub fn bellman_ford<W: Weight>(G: &impl Adjacency<W>, st: usize) -> (ShortestPath<W>, Vec<bool>) {
+    let n = G.len();
+    let mut sp = ShortestPath::_new(n, st);
+    for _ in 1..n {
+        let mut updated = false;
+        for u in 0..n {
+            if sp.dist[u] == W::inf() { continue; }
+            for &(v, w) in G.neighbors(u) {
+                if sp.dist[u] + w < sp.dist[v] {
+                    sp.dist[v] = sp.dist[u] + w;
+                    sp.prev[v] = Some(u);
+                    updated = true;
+                }
+            }
+        }
+        if !updated { break; }
+    }
+    // what can still be relaxed is on or after a negative cycle
+    let mut negative = vec![false; n];
+    for _ in 0..n {
+        let mut updated = false;
+        for u in 0..n {
+            if sp.dist[u] == W::inf() { continue; }
+            for &(v, w) in G.neighbors(u) {
+                if !negative[v] && (negative[u] || sp.dist[u] + w < sp.dist[v]) {
+                    negative[v] = true;
+                    updated = true;
+                }
+            }
+        }
+        if !updated { break; }
+    }
+    (sp, negative)
+}
+
+// the vertices of some negative cycle in order, reachable or not
+// O(nm)
+pub fn negative_cycle<W: Weight>(G: &impl Adjacency<W>) -> Option<Vec<usize>> {
+    let n = G.len();
+    // start from every vertex at once
+    let mut dist = vec![W::zero(); n];
+    let mut prev = vec![None; n];
+    let mut last = None;
+    for _ in 0..n {
+        last = None;
+        for u in 0..n {
+            for &(v, w) in G.neighbors(u) {
+                if dist[u] + w < dist[v] {
+                    dist[v] = dist[u] + w;
+                    prev[v] = Some(u);
+                    last = Some(v);
+                }
+            }
+        }
+        last?;
+    }
+    // relaxed n times, so walking back n steps lands on the cycle
+    let mut v = last?;
+    for _ in 0..n {
+        v = prev[v].unwrap();
+    }
+    let mut cycle = vec![v];
+    let mut u = prev[v].unwrap();
+    while u != v {
+        cycle.push(u);
+        u = prev[u].unwrap();
+    }
+    cycle.reverse();
+    Some(cycle)
+}
+
+// negative[i][j] is true if the distance from i to j is unbounded below, and then dist[i][j] is meaningless
+// O(n^3)
+pub fn warshall_floyd<W: Weight>(G: &impl Adjacency<W>) -> (Vec<Vec<W>>, Vec<Vec<bool>>) {
+    let N = G.len();
+    let INF = W::inf();
     let mut dist = vec![vec![INF; N]; N];
     for u in 0..N {
-        dist[u] = 0;
-        for &(v, w)in &G[u] {
-            dist[u][v] = w;
+        dist[u][u] = W::zero();
+    }
+    for u in 0..N {
+        for &(v, w) in G.neighbors(u) {
+            dist[u][v] = dist[u][v].min(w);
+        }
+    }
+    for k in 0..N {
+        for i in 0..N {
+            if dist[i][k] == INF { continue; }
+            for j in 0..N {
+                if dist[k][j] == INF { continue; }
+                let mut d = dist[i][k] + dist[k][j];
+                // keep the values above -INF, or negative cycles blow them up exponentially
+                if d < W::zero() && d < W::zero() - INF { d = W::zero() - INF; }
+                if d < dist[i][j] { dist[i][j] = d; }
+            }
         }
     }
+    let mut negative = vec![vec![false; N]; N];
     for k in 0..N {
-        for j in 0..N {
-            for i in 0..N {
-                dist[i][j] = dist[i][j].min(dist[i][k] + dist[k][j]);
+        if dist[k][k] >= W::zero() { continue; }
+        for i in 0..N {
+            if dist[i][k] == INF { continue; }
+            for j in 0..N {
+                if dist[k][j] != INF { negative[i][j] = true; }
             }
         }
     }
-    dist
+    (dist, negative)
 }
@@ -0,0 +1,26 @@
+use crate::graph::Adjacency;
+
+// Kahn's algorithm, or None if G has a cycle
+// O(n + m)
+pub fn topological_sort<W>(G: &impl Adjacency<W>) -> Option<Vec<usize>> {
+    let n = G.len();
+    let mut indeg = vec![0; n];
+    for u in 0..n {
+        for &(v, _) in G.neighbors(u) {
+            indeg[v] += 1;
+        }
+    }
+    let mut order: Vec<usize> = (0..n).filter(|&u| indeg[u] == 0).collect();
+    let mut i = 0;
+    while i < order.len() {
+        let u = order[i];
+        i += 1;
+        for &(v, _) in G.neighbors(u) {
+            indeg[v] -= 1;
+            if indeg[v] == 0 {
+                order.push(v);
+            }
+        }
+    }
+    if order.len() == n { Some(order) } else { None }
+}
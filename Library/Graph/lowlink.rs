@@ -1,3 +1,5 @@
+use crate::graph::{Adjacency, Graph};
+
 pub struct LowLink {
     pub aps: std::collections::BTreeSet<usize>,
     pub bridges: std::collections::BTreeSet<(usize, usize)>,
@@ -8,6 +10,12 @@ pub struct LowLink {
 
 impl LowLink {
     pub fn new(G: &Vec<Vec<usize>>) -> Self {
+        LowLink::from_graph(&Graph::from_lists(G))
+    }
+    // G has both directions of every edge, and the weights are dropped
+    // G may be a multigraph: each edge counts on its own, so a doubled edge is never a bridge,
+    // and the copies of the edge to the parent lower low like any back edge
+    pub fn from_graph<W>(G: &impl Adjacency<W>) -> Self {
         let mut lowlink = LowLink {
             aps: std::collections::BTreeSet::new(),
             bridges: std::collections::BTreeSet::new(),
@@ -24,38 +32,123 @@ impl LowLink {
         }
         lowlink
     }
-    fn _dfs(
+    // dfs on an explicit stack of (vertex, the next index in G[vertex], the number of children, is_aps,
+    // whether the edge to the parent is skipped), where only one edge to the parent is skipped for multiple edges
+    fn _dfs<W>(
         &mut self,
-        G: &Vec<Vec<usize>>,
-        u: usize,
+        G: &impl Adjacency<W>,
+        root: usize,
         t: &mut usize,
         visited: &mut Vec<bool>,
     ) {
-        visited[u] = true;
-        self.ord[u] = *t;
-        self.low[u] = self.ord[u];
+        visited[root] = true;
+        self.ord[root] = *t;
+        self.low[root] = self.ord[root];
         *t += 1;
-        let mut is_aps = false;
-        let mut cnt = 0;
-        for &v in &G[u] {
-            if !visited[v] {
-                self.par[v] = Some(u);
-                cnt += 1;
-                self._dfs(G, v, t, visited);
-                self.low[u] = self.low[u].min(self.low[v]);
-                if self.par[u] != None && self.ord[u] <= self.low[v] {
-                    is_aps = true;
+        let mut stack = vec![(root, 0, 0, false, false)];
+        while let Some(top) = stack.last_mut() {
+            let u = top.0;
+            if let Some(&(v, _)) = G.neighbors(u).get(top.1) {
+                top.1 += 1;
+                if !visited[v] {
+                    self.par[v] = Some(u);
+                    top.2 += 1;
+                    visited[v] = true;
+                    self.ord[v] = *t;
+                    self.low[v] = self.ord[v];
+                    *t += 1;
+                    stack.push((v, 0, 0, false, false));
+                } else if Some(v) != self.par[u] || top.4 {
+                    self.low[u] = self.low[u].min(self.ord[v]);
+                } else {
+                    top.4 = true;
+                }
+                continue;
+            }
+            let (_, _, cnt, mut is_aps, _) = stack.pop().unwrap();
+            if self.par[u] == None && cnt >= 2 { is_aps = true; }
+            if is_aps {
+                self.aps.insert(u);
+            }
+            // back in the parent, right after the child u is finished
+            if let Some((p, _, _, p_is_aps, _)) = stack.last_mut() {
+                let p = *p;
+                self.low[p] = self.low[p].min(self.low[u]);
+                if self.par[p] != None && self.ord[p] <= self.low[u] {
+                    *p_is_aps = true;
                 }
-                if self.ord[u] < self.low[v] {
-                    self.bridges.insert((u.min(v), v.max(u)));
+                if self.ord[p] < self.low[u] {
+                    self.bridges.insert((p.min(u), u.max(p)));
                 }
-            } else if Some(v) != self.par[u] {
-                self.low[u] = self.low[u].min(self.ord[v]);
             }
         }
-        if self.par[u] == None && cnt >= 2 { is_aps = true; }
-        if is_aps {
-            self.aps.insert(u);
+    }
+
+    // the vertices in the order of the visit, where each parent comes before its children
+    fn _order(&self) -> Vec<usize> {
+        let mut order = vec![0; self.ord.len()];
+        for (v, &t) in self.ord.iter().enumerate() {
+            order[t] = v;
+        }
+        order
+    }
+    // the two-edge-connected components, where cmp[v] is the component of v,
+    // and the bridge tree on the components, which is a forest if G is disconnected
+    pub fn two_edge_connected(&self) -> (Vec<usize>, Graph<()>) {
+        let n = self.ord.len();
+        let mut cmp = vec![n; n];
+        let mut num = 0;
+        let mut bridges = vec![];
+        for v in self._order() {
+            match self.par[v] {
+                Some(p) if self.ord[p] >= self.low[v] => cmp[v] = cmp[p],
+                _ => {
+                    cmp[v] = num;
+                    num += 1;
+                    if let Some(p) = self.par[v] {
+                        bridges.push((cmp[p], cmp[v], ()));
+                    }
+                }
+            }
+        }
+        (cmp, Graph::from_undirected_edges(num, &bridges))
+    }
+    // the biconnected components as the sets of vertices, where an isolated vertex is a component by itself,
+    // and the block-cut tree, whose vertices are the n vertices of G followed by the components,
+    // with an edge between v and each component containing v
+    // then w != u, v lies on every path from u to v if and only if w is on the path from u to v in the tree
+    pub fn biconnected(&self) -> (Vec<Vec<usize>>, Graph<()>) {
+        let n = self.ord.len();
+        // block[v] is the component of the edge from v to its parent
+        let mut block = vec![n; n];
+        let mut blocks: Vec<Vec<usize>> = vec![];
+        let mut isolated = vec![true; n];
+        for &p in self.par.iter().flatten() {
+            isolated[p] = false;
+        }
+        for v in self._order() {
+            match self.par[v] {
+                None => {
+                    if isolated[v] {
+                        blocks.push(vec![v]);
+                    }
+                },
+                Some(p) if self.ord[p] <= self.low[v] => {
+                    block[v] = blocks.len();
+                    blocks.push(vec![p, v]);
+                },
+                Some(p) => {
+                    block[v] = block[p];
+                    blocks[block[v]].push(v);
+                },
+            }
+        }
+        let mut tree = Graph::new(n + blocks.len());
+        for (b, vs) in blocks.iter().enumerate() {
+            for &v in vs {
+                tree.add_undirected_edge(v, n + b, ());
+            }
         }
+        (blocks, tree)
     }
 }
@@ -0,0 +1,30 @@
+#[path = "Tree.rs"]
+pub mod directed_tree;
+#[path = "Diameter.rs"]
+pub mod diameter;
+#[path = "Graph.rs"]
+pub mod base;
+#[path = "HLD.rs"]
+pub mod hld;
+#[path = "LCA.rs"]
+pub mod lca;
+#[path = "LinkCutTree.rs"]
+pub mod link_cut_tree;
+#[path = "lowlink.rs"]
+pub mod lowlink;
+#[path = "Matching.rs"]
+pub mod matching;
+#[path = "Reachability.rs"]
+pub mod reachability;
+#[path = "Rerooting.rs"]
+pub mod rerooting;
+#[path = "SCC.rs"]
+pub mod scc;
+#[path = "ShortestPath.rs"]
+pub mod shortest_path;
+#[path = "TopologicalSort.rs"]
+pub mod topological_sort;
+#[path = "2-SAT.rs"]
+pub mod two_sat;
+
+pub use base::{Adjacency, CsrGraph, Graph, Weight};
@@ -111,14 +111,14 @@ impl std::ops::DivAssign for Ratio {
 
 impl PartialOrd for Ratio {
     fn partial_cmp(&self, rhs: &Self) -> Option<std::cmp::Ordering> {
-        let left = self.0 * rhs.1;
-        let right = rhs.0 * self.1;
-        left.partial_cmp(&right)
+        Some(self.cmp(rhs))
     }
 }
 
 impl Ord for Ratio {
     fn cmp(&self, rhs: &Self) -> std::cmp::Ordering {
-        self.partial_cmp(&rhs).unwrap()
+        let left = self.0 * rhs.1;
+        let right = rhs.0 * self.1;
+        left.cmp(&right)
     }
 }
\ No newline at end of file
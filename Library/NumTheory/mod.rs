@@ -0,0 +1,10 @@
+#[path = "CRT.rs"]
+pub mod crt;
+#[path = "extendedGCD.rs"]
+pub mod extended_gcd;
+#[path = "modint.rs"]
+pub mod modint;
+#[path = "PrimeSieve.rs"]
+pub mod prime_sieve;
+#[path = "Ratio.rs"]
+pub mod ratio;
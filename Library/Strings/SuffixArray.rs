@@ -1,9 +1,4 @@
-pub fn IDCompress<T: Clone + Ord>(xs: &Vec<T>) -> Vec<usize> {
-    let xset: std::collections::BTreeSet<&T> = xs.iter().collect();
-    let xmap: std::collections::BTreeMap<&T, usize> =
-        xset.iter().enumerate().map(|(i, &x)| (x, i)).collect();
-    xs.iter().map(|x| xmap[x]).collect()
-}
+use crate::misc::id_compress::IDCompress;
 
 pub struct SuffixArray {
     pub S: Vec<usize>,
@@ -73,9 +68,9 @@ impl SuffixArray {
         for i in 0..N {
             self.ISA[self.SA[i]] = i;
         }
-        let mut h = 0;
+        let mut h: usize = 0;
         for i in 0..N {
-            if h > 0 { h -= 1; }
+            h = h.saturating_sub(1);
             if self.ISA[i] == 0 { continue; }
             let j = self.SA[self.ISA[i] - 1];
             while j + h < N && i + h < N {
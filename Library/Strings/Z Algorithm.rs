@@ -1,4 +1,4 @@
-fn Z_algorithm(S: Vec<char>) -> Vec<usize> {
+pub fn Z_algorithm(S: Vec<char>) -> Vec<usize> {
     let N = S.len();
     let mut Z = vec![0; N];
     let mut c = 0;
@@ -7,13 +7,7 @@ fn Z_algorithm(S: Vec<char>) -> Vec<usize> {
         if i + Z[l] < c + Z[c] {
             Z[i] = Z[l];
         } else {
-            let mut j = {
-                if c + Z[c] > i {
-                    c + Z[c] - i
-                } else {
-                    0
-                }
-            };
+            let mut j = (c + Z[c]).saturating_sub(i);
             while i + j < N && S[i] == S[i + j] {
                 j += 1;
             }
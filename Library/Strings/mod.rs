@@ -0,0 +1,8 @@
+#[path = "RollingHash.rs"]
+pub mod rolling_hash;
+#[path = "SuffixArray.rs"]
+pub mod suffix_array;
+#[path = "Trie.rs"]
+pub mod trie;
+#[path = "Z Algorithm.rs"]
+pub mod z_algorithm;
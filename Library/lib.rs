@@ -0,0 +1,36 @@
+#![allow(non_snake_case)]
+// the snippets are written in competitive programming style
+#![allow(
+    clippy::needless_range_loop,
+    clippy::needless_return,
+    clippy::new_without_default,
+    clippy::len_without_is_empty,
+    clippy::partialeq_to_none,
+    clippy::precedence,
+    clippy::ptr_arg,
+    clippy::manual_is_multiple_of,
+    clippy::suspicious_arithmetic_impl
+)]
+
+#[path = "Algebra/mod.rs"]
+pub mod algebra;
+#[path = "Algorithm/mod.rs"]
+pub mod algorithm;
+#[path = "DataStructure/mod.rs"]
+pub mod data_structure;
+#[path = "DP/mod.rs"]
+pub mod dp;
+#[path = "Flow/mod.rs"]
+pub mod flow;
+#[path = "FPS/mod.rs"]
+pub mod fps;
+#[path = "Geometry/mod.rs"]
+pub mod geometry;
+#[path = "Graph/mod.rs"]
+pub mod graph;
+#[path = "NumTheory/mod.rs"]
+pub mod num_theory;
+#[path = "Strings/mod.rs"]
+pub mod strings;
+#[path = "misc/mod.rs"]
+pub mod misc;
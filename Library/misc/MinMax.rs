@@ -1,3 +1,4 @@
+#[macro_export]
 macro_rules! chmin {
     ($x:expr, $($v:expr),+) => {
         $(
@@ -6,6 +7,7 @@ macro_rules! chmin {
     }
 }
 
+#[macro_export]
 macro_rules! chmax {
     ($x:expr, $($v:expr),+) => {
         $(
@@ -14,16 +16,18 @@ macro_rules! chmax {
     }
 }
 
+#[macro_export]
 macro_rules! min {
     ($x:expr) => ( $x );
     ($x:expr, $($xs:expr),+) => {
-        std::cmp::min($x, min!( $($xs),+ ))
+        std::cmp::min($x, $crate::min!( $($xs),+ ))
     };
 }
 
+#[macro_export]
 macro_rules! max {
     ($x:expr) => ( $x );
     ($x:expr, $($xs:expr),+) => {
-        std::cmp::max($x, max!( $($xs),+ ))
+        std::cmp::max($x, $crate::max!( $($xs),+ ))
     };
 }
\ No newline at end of file
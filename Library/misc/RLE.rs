@@ -1,4 +1,4 @@
-fn RLE<T: Clone + Copy + Eq>(vector: &[T]) -> Vec<(T, usize)> {
+pub fn RLE<T: Clone + Copy + Eq>(vector: &[T]) -> Vec<(T, usize)> {
     let mut ret = vec![(vector[0], 0)];
     for &v in vector {
         let (x, cnt) = ret.pop().unwrap();
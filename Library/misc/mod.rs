@@ -0,0 +1,10 @@
+#[path = "binary_search.rs"]
+pub mod binary_search;
+#[path = "IDCompress.rs"]
+pub mod id_compress;
+#[path = "is_palindrome.rs"]
+pub mod is_palindrome;
+#[path = "MinMax.rs"]
+pub mod min_max;
+#[path = "RLE.rs"]
+pub mod rle;
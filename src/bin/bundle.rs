@@ -0,0 +1,516 @@
+// bundle a solution file and the library modules it reaches into one submission file
+//
+// usage: cargo run --bin bundle -- <solution.rs> [-o <output.rs>]
+//
+// the solution refers to the library as `algorithms::...`.
+// every module reached from the solution (transitively through `crate::`, `super::`, `self::`
+// and `$crate::` paths inside the library) is inlined once, keeping the library's module tree,
+// so that `algorithms::x::y` can simply be rewritten to `crate::x::y`.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+const CRATE_NAME: &str = "algorithms";
+
+// ----- begin module tree -----
+struct Module {
+    name: String,
+    file: PathBuf,
+    parent: Option<usize>,
+    children: Vec<usize>,
+}
+
+struct ModuleTree {
+    modules: Vec<Module>,
+}
+
+impl ModuleTree {
+    fn load(root: &Path) -> Self {
+        let mut tree = ModuleTree { modules: vec![] };
+        tree._load(String::new(), root.to_path_buf(), None);
+        tree
+    }
+    fn _load(&mut self, name: String, file: PathBuf, parent: Option<usize>) -> usize {
+        let id = self.modules.len();
+        self.modules.push(Module { name, file: file.clone(), parent, children: vec![] });
+        let dir = file.parent().unwrap().to_path_buf();
+        for (path, name) in mod_declarations(&read(&file)) {
+            let child = self._load(name, dir.join(path), Some(id));
+            self.modules[id].children.push(child);
+        }
+        id
+    }
+    fn child(&self, id: usize, name: &str) -> Option<usize> {
+        self.modules[id].children.iter().copied().find(|&c| self.modules[c].name == name)
+    }
+    fn subtree(&self, id: usize, res: &mut Vec<usize>) {
+        res.push(id);
+        for &c in &self.modules[id].children {
+            self.subtree(c, res);
+        }
+    }
+}
+
+// pairs of (`#[path = "..."]`, `pub mod name;`)
+fn mod_declarations(src: &str) -> Vec<(String, String)> {
+    let lines: Vec<&str> = src.lines().map(|l| l.trim()).collect();
+    let mut res = vec![];
+    for w in lines.windows(2) {
+        if let (Some(path), Some(name)) = (path_attribute(w[0]), mod_item(w[1])) {
+            res.push((path, name));
+        }
+    }
+    res
+}
+
+fn path_attribute(line: &str) -> Option<String> {
+    let rest = line.strip_prefix("#[path")?.trim_start().strip_prefix('=')?.trim_start();
+    let rest = rest.strip_prefix('"')?;
+    Some(rest[..rest.find('"')?].to_string())
+}
+
+fn mod_item(line: &str) -> Option<String> {
+    let name = line.strip_prefix("pub mod ")?.strip_suffix(';')?.trim();
+    Some(name.to_string())
+}
+// ----- end module tree -----
+
+// ----- begin tokenizer -----
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Punct(&'static str),
+    Other,
+}
+
+// tokens and their spans (as char indices)
+fn tokenize(src: &str) -> (Vec<Token>, Vec<(usize, usize)>) {
+    let s: Vec<char> = src.chars().collect();
+    let n = s.len();
+    let mut tokens = vec![];
+    let mut spans = vec![];
+    let mut i = 0;
+    while i < n {
+        let c = s[i];
+        let st = i;
+        let len = tokens.len();
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '/' && i + 1 < n && s[i + 1] == '/' {
+            while i < n && s[i] != '\n' { i += 1; }
+        } else if c == '/' && i + 1 < n && s[i + 1] == '*' {
+            let mut depth = 0;
+            while i < n {
+                if s[i] == '/' && i + 1 < n && s[i + 1] == '*' {
+                    depth += 1;
+                    i += 2;
+                } else if s[i] == '*' && i + 1 < n && s[i + 1] == '/' {
+                    depth -= 1;
+                    i += 2;
+                    if depth == 0 { break; }
+                } else {
+                    i += 1;
+                }
+            }
+        } else if c == 'r' && i + 1 < n && (s[i + 1] == '"' || s[i + 1] == '#') && {
+            let mut j = i + 1;
+            while j < n && s[j] == '#' { j += 1; }
+            j < n && s[j] == '"'
+        } {
+            // raw string literal
+            let mut j = i + 1;
+            let mut hashes = 0;
+            while s[j] == '#' { hashes += 1; j += 1; }
+            j += 1;
+            while j < n && !(s[j] == '"' && (1..=hashes).all(|k| j + k < n && s[j + k] == '#')) {
+                j += 1;
+            }
+            i = j + 1 + hashes;
+            tokens.push(Token::Other);
+        } else if c == '"' {
+            i += 1;
+            while i < n && s[i] != '"' {
+                if s[i] == '\\' { i += 1; }
+                i += 1;
+            }
+            i += 1;
+            tokens.push(Token::Other);
+        } else if c == '\'' {
+            if i + 1 < n && s[i + 1] == '\\' {
+                // escaped char literal
+                i += 2;
+                while i < n && s[i] != '\'' { i += 1; }
+                i += 1;
+            } else if i + 2 < n && s[i + 2] == '\'' {
+                i += 3;
+            } else {
+                // lifetime
+                i += 1;
+            }
+            tokens.push(Token::Other);
+        } else if c == '$' || c == '_' || c.is_alphanumeric() {
+            i += 1;
+            while i < n && (s[i] == '_' || s[i].is_alphanumeric()) { i += 1; }
+            tokens.push(Token::Ident(s[st..i].iter().collect()));
+        } else if c == ':' && i + 1 < n && s[i + 1] == ':' {
+            i += 2;
+            tokens.push(Token::Punct("::"));
+        } else {
+            i += 1;
+            tokens.push(match c {
+                '{' => Token::Punct("{"),
+                '}' => Token::Punct("}"),
+                ',' => Token::Punct(","),
+                '*' => Token::Punct("*"),
+                ';' => Token::Punct(";"),
+                _ => Token::Other,
+            });
+        }
+        if tokens.len() > len {
+            spans.push((st, i));
+        }
+    }
+    (tokens, spans)
+}
+
+fn ident(t: Option<&Token>) -> Option<&str> {
+    match t {
+        Some(Token::Ident(s)) => Some(s),
+        _ => None,
+    }
+}
+
+struct UsePath {
+    path: Vec<String>,
+    is_glob: bool,
+    alias: Option<String>,
+}
+
+fn parse_use_tree(tokens: &[Token], i: &mut usize, prefix: &mut Vec<String>, res: &mut Vec<UsePath>) {
+    let depth = prefix.len();
+    if tokens.get(*i) == Some(&Token::Punct("::")) { *i += 1; }
+    loop {
+        match tokens.get(*i) {
+            Some(Token::Ident(s)) => {
+                // `a::{self, b}` imports `a` itself
+                if s != "self" || prefix.is_empty() {
+                    prefix.push(s.clone());
+                }
+                *i += 1;
+                if tokens.get(*i) == Some(&Token::Punct("::")) {
+                    *i += 1;
+                } else {
+                    let mut alias = None;
+                    if ident(tokens.get(*i)) == Some("as") {
+                        alias = ident(tokens.get(*i + 1)).map(|s| s.to_string());
+                        *i += 2;
+                    }
+                    res.push(UsePath { path: prefix.clone(), is_glob: false, alias });
+                    break;
+                }
+            }
+            Some(Token::Punct("*")) => {
+                *i += 1;
+                res.push(UsePath { path: prefix.clone(), is_glob: true, alias: None });
+                break;
+            }
+            Some(Token::Punct("{")) => {
+                *i += 1;
+                while *i < tokens.len() && tokens[*i] != Token::Punct("}") {
+                    parse_use_tree(tokens, i, prefix, res);
+                    if tokens.get(*i) == Some(&Token::Punct(",")) { *i += 1; }
+                }
+                *i += 1;
+                break;
+            }
+            _ => break,
+        }
+    }
+    prefix.truncate(depth);
+}
+
+// all the paths (as (path, is_glob)) written in the source
+fn collect_paths(src: &str) -> Vec<(Vec<String>, bool)> {
+    let (tokens, _) = tokenize(src);
+    let mut res = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        let after_colons = i > 0 && tokens[i - 1] == Token::Punct("::");
+        match ident(tokens.get(i)) {
+            Some("use") if !after_colons => {
+                i += 1;
+                let mut uses = vec![];
+                parse_use_tree(&tokens, &mut i, &mut vec![], &mut uses);
+                res.extend(uses.into_iter().map(|u| (u.path, u.is_glob)));
+            }
+            Some(s) if !after_colons => {
+                let mut path = vec![s.to_string()];
+                i += 1;
+                while tokens.get(i) == Some(&Token::Punct("::")) {
+                    match ident(tokens.get(i + 1)) {
+                        Some(s) => {
+                            path.push(s.to_string());
+                            i += 2;
+                        }
+                        None => break,
+                    }
+                }
+                if path.len() >= 2 {
+                    res.push((path, false));
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    res
+}
+
+// names of `#[macro_export] macro_rules! name`
+fn exported_macros(src: &str) -> Vec<String> {
+    let lines: Vec<&str> = src.lines().map(|l| l.trim()).collect();
+    let mut res = vec![];
+    for w in lines.windows(2) {
+        if w[0] != "#[macro_export]" { continue; }
+        if let Some(rest) = w[1].strip_prefix("macro_rules!") {
+            let name: String = rest.trim().chars().take_while(|&c| c == '_' || c.is_alphanumeric()).collect();
+            res.push(name);
+        }
+    }
+    res
+}
+// ----- end tokenizer -----
+
+// ----- begin bundler -----
+struct Bundler {
+    tree: ModuleTree,
+    macros: HashMap<String, usize>,
+    included: Vec<bool>,
+    scanned: Vec<bool>,
+    stack: Vec<usize>,
+}
+
+impl Bundler {
+    fn new(tree: ModuleTree) -> Self {
+        let n = tree.modules.len();
+        let mut macros = HashMap::new();
+        for id in 0..n {
+            for name in exported_macros(&read(&tree.modules[id].file)) {
+                macros.insert(name, id);
+            }
+        }
+        Bundler { tree, macros, included: vec![false; n], scanned: vec![false; n], stack: vec![] }
+    }
+
+    fn include(&mut self, id: usize) {
+        let mut v = Some(id);
+        while let Some(u) = v {
+            if !self.included[u] {
+                self.included[u] = true;
+                self.stack.push(u);
+            }
+            v = self.tree.modules[u].parent;
+        }
+    }
+
+    // resolve the paths written in the module `current` (None for the solution)
+    fn resolve(&mut self, current: Option<usize>, src: &str) {
+        let paths = collect_paths(src);
+        // modules brought into scope by glob imports can be referred by their names
+        let mut scope: Vec<usize> = current.into_iter().collect();
+        for (path, is_glob) in &paths {
+            if *is_glob {
+                if let Some((id, rest)) = self.locate(current, &scope, path) {
+                    if rest.is_empty() { scope.push(id); }
+                }
+            }
+        }
+        for (path, is_glob) in &paths {
+            let Some((id, rest)) = self.locate(current, &scope, path) else { continue };
+            if !rest.is_empty() {
+                if id == 0 {
+                    if let Some(&m) = self.macros.get(&rest[0]) { self.include(m); }
+                }
+                self.include(id);
+            } else if *is_glob {
+                self.include(id);
+            } else {
+                let mut sub = vec![];
+                self.tree.subtree(id, &mut sub);
+                for m in sub {
+                    self.include(m);
+                }
+            }
+        }
+    }
+
+    // deepest module pointed by the path, and the rest of the path
+    fn locate<'a>(&self, current: Option<usize>, scope: &[usize], path: &'a [String]) -> Option<(usize, &'a [String])> {
+        let (mut id, mut k) = match (path[0].as_str(), current) {
+            (CRATE_NAME, None) => (0, 1),
+            ("crate" | "$crate", Some(_)) => (0, 1),
+            ("self", Some(c)) => (c, 1),
+            ("super", Some(c)) => (self.tree.modules[c].parent?, 1),
+            _ => (scope.iter().find_map(|&s| self.tree.child(s, &path[0]))?, 1),
+        };
+        while k < path.len() && path[k] == "super" {
+            id = self.tree.modules[id].parent?;
+            k += 1;
+        }
+        while k < path.len() {
+            match self.tree.child(id, &path[k]) {
+                Some(c) => id = c,
+                None => break,
+            }
+            k += 1;
+        }
+        Some((id, &path[k..]))
+    }
+
+    fn run(&mut self, solution: &str) {
+        self.resolve(None, solution);
+        while let Some(id) = self.stack.pop() {
+            if self.scanned[id] { continue; }
+            self.scanned[id] = true;
+            if id == 0 { continue; }
+            let src = read(&self.tree.modules[id].file);
+            self.resolve(Some(id), &src);
+        }
+    }
+
+    // `use algorithms::...;` -> `use crate::...;`
+    // except for the macros and the top-level modules, which are already at the root
+    fn rewrite_solution(&self, src: &str) -> String {
+        let (tokens, spans) = tokenize(src);
+        let s: Vec<char> = src.chars().collect();
+        let mut res = String::new();
+        let mut last = 0;
+        let mut i = 0;
+        while i < tokens.len() {
+            let is_use = ident(tokens.get(i)) == Some("use") && (i == 0 || tokens[i - 1] != Token::Punct("::"));
+            let target = if tokens.get(i + 1) == Some(&Token::Punct("::")) { i + 2 } else { i + 1 };
+            if !is_use || ident(tokens.get(target)) != Some(CRATE_NAME) {
+                i += 1;
+                continue;
+            }
+            let mut j = i + 1;
+            let mut uses = vec![];
+            parse_use_tree(&tokens, &mut j, &mut vec![], &mut uses);
+            let stmts: Vec<String> = uses.iter()
+                .filter(|u| {
+                    let at_root = u.path.len() == 2 && !u.is_glob && u.alias.is_none();
+                    !(at_root && (self.macros.contains_key(&u.path[1]) || self.tree.child(0, &u.path[1]).is_some()))
+                })
+                .map(|u| {
+                    let mut stmt = format!("use crate::{}", u.path[1..].join("::"));
+                    if u.is_glob { stmt.push_str("::*"); }
+                    if let Some(alias) = &u.alias { stmt.push_str(&format!(" as {}", alias)); }
+                    stmt + ";"
+                })
+                .collect();
+            let end = if tokens.get(j) == Some(&Token::Punct(";")) { spans[j].1 } else { spans[j - 1].1 };
+            res.extend(&s[last..spans[i].0]);
+            res.push_str(&stmts.join(" "));
+            last = end;
+            i = j + 1;
+        }
+        res.extend(&s[last..]);
+        let mut out = String::new();
+        for line in res.lines() {
+            if line.trim() == format!("extern crate {};", CRATE_NAME) { continue; }
+            let mut rest = line;
+            while let Some(p) = rest.find(CRATE_NAME) {
+                let head_ok = rest[..p].chars().next_back().is_none_or(|c| !(c == '_' || c.is_alphanumeric()));
+                let tail_ok = rest[p + CRATE_NAME.len()..].starts_with("::");
+                out.push_str(&rest[..p]);
+                out.push_str(if head_ok && tail_ok { "crate" } else { CRATE_NAME });
+                rest = &rest[p + CRATE_NAME.len()..];
+            }
+            out.push_str(rest);
+            out.push('\n');
+        }
+        out
+    }
+
+    fn emit(&self, id: usize, depth: usize, out: &mut String) {
+        let src = read(&self.tree.modules[id].file);
+        let indent = "    ".repeat(depth);
+        let lines: Vec<&str> = src.lines().collect();
+        let mut i = 0;
+        while i < lines.len() {
+            let decl = (i + 1 < lines.len())
+                .then(|| path_attribute(lines[i].trim()).and(mod_item(lines[i + 1].trim())))
+                .flatten();
+            if let Some(name) = decl {
+                let child = self.tree.child(id, &name).unwrap();
+                if self.included[child] {
+                    self.emit_module(child, depth, out);
+                }
+                i += 2;
+                continue;
+            }
+            if id != 0 && !lines[i].trim_start().starts_with("#![") {
+                if lines[i].trim().is_empty() {
+                    out.push('\n');
+                } else {
+                    out.push_str(&format!("{}{}\n", indent, lines[i]));
+                }
+            }
+            i += 1;
+        }
+    }
+
+    fn emit_module(&self, id: usize, depth: usize, out: &mut String) {
+        let indent = "    ".repeat(depth);
+        if depth == 0 {
+            out.push_str("#[allow(dead_code, unused_imports, unused_macros, non_snake_case)]\n");
+        }
+        out.push_str(&format!("{}pub mod {} {{\n", indent, self.tree.modules[id].name));
+        self.emit(id, depth + 1, out);
+        out.push_str(&format!("{}}}\n", indent));
+    }
+}
+// ----- end bundler -----
+
+fn read(path: &Path) -> String {
+    std::fs::read_to_string(path).unwrap_or_else(|e| panic!("cannot read {}: {}", path.display(), e))
+}
+
+pub fn bundle(solution: &str, library_root: &Path) -> String {
+    let mut bundler = Bundler::new(ModuleTree::load(library_root));
+    bundler.run(solution);
+    let mut out = bundler.rewrite_solution(solution);
+    let mut library = String::new();
+    bundler.emit(0, 0, &mut library);
+    if !library.is_empty() {
+        out.push_str("\n// ----- begin library -----\n");
+        out.push_str(&library);
+        out.push_str("// ----- end library -----\n");
+    }
+    out
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let mut input = None;
+    let mut output = None;
+    let mut i = 0;
+    while i < args.len() {
+        if args[i] == "-o" {
+            output = args.get(i + 1).cloned();
+            i += 2;
+        } else {
+            input = Some(args[i].clone());
+            i += 1;
+        }
+    }
+    let Some(input) = input else {
+        eprintln!("usage: bundle <solution.rs> [-o <output.rs>]");
+        std::process::exit(1);
+    };
+    let library_root = Path::new(env!("CARGO_MANIFEST_DIR")).join("Library/lib.rs");
+    let res = bundle(&read(Path::new(&input)), &library_root);
+    match output {
+        Some(path) => std::fs::write(&path, res).unwrap_or_else(|e| panic!("cannot write {}: {}", path, e)),
+        None => print!("{}", res),
+    }
+}
@@ -0,0 +1,64 @@
+// bundle a small solution with the bundle binary, and compile and run the output with rustc
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const SOLUTION: &str = r#"use algorithms::algebra::*;
+use algorithms::data_structure::seg_tree::SegTree;
+use algorithms::graph::scc::SCC;
+
+fn main() {
+    let seg = SegTree::from(vec![MinMonoid(3), MinMonoid(1), MinMonoid(4)]);
+    let mut scc = SCC::from(3, vec![(0, 1), (1, 0), (1, 2)]);
+    println!("{} {:?}", seg.fold(0, 3).0, scc.cmp());
+}
+"#;
+
+// the rlib of an external crate among the dependencies built next to this test
+fn find_rlib(deps: &Path, name: &str) -> PathBuf {
+    let prefix = format!("lib{}-", name);
+    std::fs::read_dir(deps).unwrap()
+        .map(|e| e.unwrap().path())
+        .find(|p| {
+            let file = p.file_name().unwrap().to_str().unwrap();
+            file.starts_with(&prefix) && file.ends_with(".rlib")
+        })
+        .unwrap_or_else(|| panic!("no rlib of {} in {}", name, deps.display()))
+}
+
+#[test]
+fn bundle_compiles() {
+    let dir = std::env::temp_dir().join(format!("bundle_test_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let solution = dir.join("main.rs");
+    let bundled = dir.join("bundled.rs");
+    std::fs::write(&solution, SOLUTION).unwrap();
+
+    let status = Command::new(env!("CARGO_BIN_EXE_bundle")).arg(&solution).arg("-o").arg(&bundled).status().unwrap();
+    assert!(status.success());
+    let out = std::fs::read_to_string(&bundled).unwrap();
+
+    // only the reached modules are inlined, and algebra once though both seg_tree and the solution use it
+    for reached in ["algebra", "monoid", "data_structure", "seg_tree", "graph", "scc", "base"] {
+        assert!(out.contains(&format!("pub mod {} {{", reached)), "{} is missing", reached);
+    }
+    for unreached in ["lazy_seg_tree", "hld", "flow", "maxflow", "strings", "two_sat"] {
+        assert!(!out.contains(&format!("pub mod {} {{", unreached)), "{} is inlined", unreached);
+    }
+    assert_eq!(out.matches("pub mod algebra {").count(), 1);
+    assert!(!out.contains("algorithms::"));
+
+    // the library reached uses proconio
+    let deps = std::env::current_exe().unwrap().parent().unwrap().to_path_buf();
+    let exe = dir.join("bundled");
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let output = Command::new(rustc)
+        .args(["--edition", "2021", "-A", "warnings", "-o"]).arg(&exe).arg(&bundled)
+        .arg("-L").arg(format!("dependency={}", deps.display()))
+        .arg("--extern").arg(format!("proconio={}", find_rlib(&deps, "proconio").display()))
+        .output().unwrap();
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let run = Command::new(&exe).output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&run.stdout), "1 [0, 0, 1]\n");
+    std::fs::remove_dir_all(&dir).ok();
+}
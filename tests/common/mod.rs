@@ -0,0 +1,88 @@
+// randomized oracle testing
+// generate a sequence of operations, run it against the structure and a naive model,
+// and shrink the sequence to a minimal reproduction when they disagree
+
+#![allow(dead_code)]
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+pub const CASES: u64 = 200;
+
+pub fn expect<T: PartialEq + std::fmt::Debug>(got: T, want: T, what: &str) -> Result<(), String> {
+    if got == want {
+        Ok(())
+    } else {
+        Err(format!("{}: got {:?}, want {:?}", what, got, want))
+    }
+}
+
+thread_local! {
+    static SILENT: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+// panics caught while shrinking are reported once at the end, not printed one by one
+fn silence_caught_panics() {
+    static ONCE: std::sync::Once = std::sync::Once::new();
+    ONCE.call_once(|| {
+        let default = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !SILENT.with(|s| s.get()) {
+                default(info);
+            }
+        }));
+    });
+}
+
+fn run_caught<P, O>(run: &impl Fn(&P, &[O]) -> Result<(), String>, params: &P, ops: &[O]) -> Result<(), String> {
+    SILENT.with(|s| s.set(true));
+    let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| run(params, ops)));
+    SILENT.with(|s| s.set(false));
+    match res {
+        Ok(res) => res,
+        Err(e) => {
+            let msg = e.downcast_ref::<String>().cloned()
+                .or_else(|| e.downcast_ref::<&str>().map(|s| s.to_string()))
+                .unwrap_or_default();
+            Err(format!("panicked: {}", msg))
+        }
+    }
+}
+
+// remove chunks of operations as long as the failure reproduces
+fn shrink<P, O: Clone>(run: &impl Fn(&P, &[O]) -> Result<(), String>, params: &P, mut ops: Vec<O>) -> (Vec<O>, String) {
+    let mut err = run_caught(run, params, &ops).unwrap_err();
+    let mut chunk = ops.len().max(1);
+    while chunk > 0 {
+        let mut i = 0;
+        while i < ops.len() {
+            let mut cand = ops[..i].to_vec();
+            cand.extend_from_slice(&ops[(i + chunk).min(ops.len())..]);
+            match run_caught(run, params, &cand) {
+                Err(e) => { ops = cand; err = e; },
+                Ok(()) => { i += chunk; },
+            }
+        }
+        chunk /= 2;
+    }
+    (ops, err)
+}
+
+pub fn check<P, O>(
+    name: &str,
+    gen: impl Fn(&mut StdRng) -> (P, Vec<O>),
+    run: impl Fn(&P, &[O]) -> Result<(), String>,
+)
+where
+    P: std::fmt::Debug,
+    O: Clone + std::fmt::Debug,
+{
+    silence_caught_panics();
+    for seed in 0..CASES {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let (params, ops) = gen(&mut rng);
+        if run_caught(&run, &params, &ops).is_ok() { continue; }
+        let (ops, err) = shrink(&run, &params, ops);
+        panic!("{} (seed {}): {}\nparams: {:?}\nops: {:?}", name, seed, err, params, ops);
+    }
+}
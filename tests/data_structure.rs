@@ -0,0 +1,1181 @@
+mod common;
+
+use algorithms::algebra::*;
+use algorithms::data_structure::binary_trie::BinaryTrie;
+use algorithms::data_structure::bit::{BIT, BIT2D, CompressedBIT2D, RangeBIT};
+use algorithms::data_structure::counter::Counter;
+use algorithms::data_structure::disjoint_sparse_table::DisjointSparseTable;
+use algorithms::data_structure::dynamic_seg_tree::DynamicSegTree;
+use algorithms::data_structure::lazy_seg_tree::LazySegTree;
+use algorithms::data_structure::merge_sort_tree::MergeSortTree;
+use algorithms::data_structure::multiset::MultiSet;
+use algorithms::data_structure::offline_dynamic_connectivity::OfflineDynamicConnectivity;
+use algorithms::data_structure::persistent_seg_tree::PersistentSegTree;
+use algorithms::data_structure::potential_union_find::{Contradiction, PotentialUnionFind};
+use algorithms::data_structure::rollback_union_find::RollbackUnionFind;
+use algorithms::data_structure::seg_tree::SegTree;
+use algorithms::data_structure::seg_tree_beats::{SegTreeBeats, SumMinMax};
+use algorithms::data_structure::sparse_table::SparseTable;
+use algorithms::data_structure::union_find::UnionFind;
+use common::{check, expect};
+use rand::Rng;
+use std::collections::BTreeMap;
+
+const MOD: u64 = 998_244_353;
+
+// ----- begin test monoids -----
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Min(i64);
+impl Monoid for Min {
+    fn one() -> Self { Min(i64::MAX) }
+    fn ope(&self, rhs: &Self) -> Self { Min(self.0.min(rhs.0)) }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Add(i64);
+impl Monoid for Add {
+    fn one() -> Self { Add(0) }
+    fn ope(&self, rhs: &Self) -> Self { Add(self.0 + rhs.0) }
+}
+impl Act<Min> for Add {
+    fn act(&self, x: Min) -> Min {
+        if x.0 == i64::MAX { x } else { Min(x.0 + self.0) }
+    }
+}
+
+// (sum, length)
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Sum(i64, i64);
+impl Monoid for Sum {
+    fn one() -> Self { Sum(0, 0) }
+    fn ope(&self, rhs: &Self) -> Self { Sum(self.0 + rhs.0, self.1 + rhs.1) }
+}
+
+// the later assignment wins
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Assign(Option<i64>);
+impl Monoid for Assign {
+    fn one() -> Self { Assign(None) }
+    fn ope(&self, rhs: &Self) -> Self { if rhs.0.is_some() { *rhs } else { *self } }
+}
+impl Act<Sum> for Assign {
+    fn act(&self, x: Sum) -> Sum {
+        match self.0 { Some(a) => Sum(a * x.1, x.1), None => x }
+    }
+}
+
+// x -> a * x + b, composed from left to right
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Affine(u64, u64);
+impl Monoid for Affine {
+    fn one() -> Self { Affine(1, 0) }
+    fn ope(&self, rhs: &Self) -> Self { Affine(self.0 * rhs.0 % MOD, (self.1 * rhs.0 + rhs.1) % MOD) }
+}
+// ----- end test monoids -----
+
+fn fold_naive<X: Monoid>(xs: &[X]) -> X {
+    xs.iter().fold(X::one(), |acc, x| acc.ope(x))
+}
+
+fn range(rng: &mut impl Rng, n: usize) -> (usize, usize) {
+    let l = rng.gen_range(0..=n);
+    let r = rng.gen_range(l..=n);
+    (l, r)
+}
+
+#[derive(Debug, Clone)]
+enum SegOp<X> {
+    Update(usize, X),
+    Fold(usize, usize),
+}
+
+fn check_seg_tree<X>(
+    name: &str,
+    gen_x: impl Fn(&mut rand::rngs::StdRng) -> X,
+    naive_fold: impl Fn(&[X]) -> X,
+)
+where
+    X: Copy + Monoid + PartialEq + std::fmt::Debug,
+{
+    check(
+        name,
+        |rng| {
+            let n = rng.gen_range(1..40);
+            let init: Vec<X> = (0..n).map(|_| gen_x(rng)).collect();
+            let ops = (0..100).map(|_| {
+                if rng.gen_bool(0.5) {
+                    SegOp::Update(rng.gen_range(0..n), gen_x(rng))
+                } else {
+                    let (l, r) = range(rng, n);
+                    SegOp::Fold(l, r)
+                }
+            }).collect();
+            (init, ops)
+        },
+        |init, ops| {
+            let mut naive = init.clone();
+            let mut seg = SegTree::from(init.clone());
+            for op in ops {
+                match *op {
+                    SegOp::Update(i, x) => { naive[i] = x; seg.update(i, x); },
+                    SegOp::Fold(l, r) => expect(seg.fold(l, r), naive_fold(&naive[l..r]), "fold")?,
+                }
+            }
+            for i in 0..naive.len() {
+                expect(seg[i], naive[i], "index")?;
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn seg_tree_min() {
+    check_seg_tree(
+        "seg_tree_min",
+        |rng| MinMonoid(rng.gen_range(0..100)),
+        |xs| MinMonoid(xs.iter().map(|x| x.0).min().unwrap_or(usize::MAX)),
+    );
+}
+
+#[test]
+fn seg_tree_max() {
+    check_seg_tree(
+        "seg_tree_max",
+        |rng| MaxMonoid(rng.gen_range(0..100)),
+        |xs| MaxMonoid(xs.iter().map(|x| x.0).max().unwrap_or(0)),
+    );
+}
+
+#[test]
+fn seg_tree_sum() {
+    check_seg_tree(
+        "seg_tree_sum",
+        |rng| SumMonoid(rng.gen_range(0..100)),
+        |xs| SumMonoid(xs.iter().map(|x| x.0).sum()),
+    );
+}
+
+#[test]
+fn seg_tree_affine() {
+    check_seg_tree(
+        "seg_tree_affine",
+        |rng| Affine(rng.gen_range(0..MOD), rng.gen_range(0..MOD)),
+        |xs| {
+            // evaluate the composition at two points to recover (a, b)
+            let apply = |x: u64| xs.iter().fold(x, |x, f| (f.0 * x + f.1) % MOD);
+            let b = apply(0);
+            Affine((apply(1) + MOD - b) % MOD, b)
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum SearchOp<X> {
+    Update(usize, X),
+    MaxRight(usize, X),
+    MinLeft(usize, X),
+}
+
+// pred(t, x) is monotone in x, so max_right / min_left can be compared against a linear scan
+fn check_seg_tree_search<X>(
+    name: &str,
+    gen_x: impl Fn(&mut rand::rngs::StdRng) -> X,
+    pred: impl Fn(&X, &X) -> bool,
+)
+where
+    X: Copy + Monoid + PartialEq + std::fmt::Debug,
+{
+    check(
+        name,
+        |rng| {
+            let n = rng.gen_range(1..40);
+            let init: Vec<X> = (0..n).map(|_| gen_x(rng)).collect();
+            let ops = (0..100).map(|_| match rng.gen_range(0..3) {
+                0 => SearchOp::Update(rng.gen_range(0..n), gen_x(rng)),
+                1 => SearchOp::MaxRight(rng.gen_range(0..=n), gen_x(rng)),
+                _ => SearchOp::MinLeft(rng.gen_range(0..=n), gen_x(rng)),
+            }).collect();
+            (init, ops)
+        },
+        |init, ops| {
+            let n = init.len();
+            let mut naive = init.clone();
+            let mut seg = SegTree::from(init.clone());
+            for op in ops {
+                match *op {
+                    SearchOp::Update(i, x) => { naive[i] = x; seg.update(i, x); },
+                    SearchOp::MaxRight(l, t) => {
+                        let want = (l..=n).rev().find(|&r| pred(&t, &fold_naive(&naive[l..r]))).unwrap();
+                        expect(seg.max_right(l, |x| pred(&t, x)), want, "max_right")?;
+                    },
+                    SearchOp::MinLeft(r, t) => {
+                        let want = (0..=r).find(|&l| pred(&t, &fold_naive(&naive[l..r]))).unwrap();
+                        expect(seg.min_left(r, |x| pred(&t, x)), want, "min_left")?;
+                    },
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn seg_tree_search_min() {
+    // the range minimum stays at least t
+    check_seg_tree_search("seg_tree_search_min", |rng| MinMonoid(rng.gen_range(0..100)), |t, x| x.0 >= t.0);
+}
+
+#[test]
+fn seg_tree_search_sum() {
+    // the range sum stays at most t, i.e. k-th one search
+    check_seg_tree_search("seg_tree_search_sum", |rng| SumMonoid(rng.gen_range(0..20)), |t, x| x.0 <= t.0 * 5);
+}
+
+// static tables only answer folds
+fn check_static_fold<X, T>(
+    name: &str,
+    gen_x: impl Fn(&mut rand::rngs::StdRng) -> X,
+    build: impl Fn(Vec<X>) -> T,
+    fold: impl Fn(&T, usize, usize) -> X,
+)
+where
+    X: Copy + Monoid + PartialEq + std::fmt::Debug,
+{
+    check(
+        name,
+        |rng| {
+            let n = rng.gen_range(0..70);
+            let xs: Vec<X> = (0..n).map(|_| gen_x(rng)).collect();
+            let ops: Vec<(usize, usize)> = (0..100).map(|_| range(rng, n)).collect();
+            (xs, ops)
+        },
+        |xs, ops| {
+            let table = build(xs.clone());
+            for &(l, r) in ops {
+                expect(fold(&table, l, r), fold_naive(&xs[l..r]), "fold")?;
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn sparse_table() {
+    check_static_fold(
+        "sparse_table",
+        |rng| Min(rng.gen_range(-50..50)),
+        SparseTable::from,
+        |table, l, r| table.fold(l, r),
+    );
+}
+
+#[test]
+fn disjoint_sparse_table() {
+    check_static_fold(
+        "disjoint_sparse_table",
+        |rng| Affine(rng.gen_range(0..MOD), rng.gen_range(0..MOD)),
+        DisjointSparseTable::from,
+        |table, l, r| table.fold(l, r),
+    );
+}
+
+#[derive(Debug, Clone)]
+enum LazyOp<X, M> {
+    RangeUpdate(usize, usize, M),
+    SetVal(usize, X),
+    Fold(usize, usize),
+    Get(usize),
+    MaxRight(usize, X),
+    MinLeft(usize, X),
+}
+
+// pred(t, x) is monotone in x, as in check_seg_tree_search
+fn check_lazy_seg_tree<X, M>(
+    name: &str,
+    gen_x: impl Fn(&mut rand::rngs::StdRng) -> X,
+    gen_m: impl Fn(&mut rand::rngs::StdRng) -> M,
+    pred: impl Fn(&X, &X) -> bool,
+)
+where
+    X: Copy + Monoid + PartialEq + std::fmt::Debug,
+    M: Copy + Monoid + Act<X> + std::fmt::Debug,
+{
+    check(
+        name,
+        |rng| {
+            let n = rng.gen_range(1..40);
+            let init: Vec<X> = (0..n).map(|_| gen_x(rng)).collect();
+            let ops = (0..100).map(|_| match rng.gen_range(0..6) {
+                0 => { let (l, r) = range(rng, n); LazyOp::RangeUpdate(l, r, gen_m(rng)) },
+                1 => LazyOp::SetVal(rng.gen_range(0..n), gen_x(rng)),
+                2 => { let (l, r) = range(rng, n); LazyOp::Fold(l, r) },
+                3 => LazyOp::Get(rng.gen_range(0..n)),
+                4 => LazyOp::MaxRight(rng.gen_range(0..=n), gen_x(rng)),
+                _ => LazyOp::MinLeft(rng.gen_range(0..=n), gen_x(rng)),
+            }).collect();
+            (init, ops)
+        },
+        |init, ops| {
+            let n = init.len();
+            let mut naive = init.clone();
+            let mut seg = LazySegTree::<X, M>::new(n);
+            seg.build(init.clone());
+            for op in ops {
+                match *op {
+                    LazyOp::RangeUpdate(l, r, m) => {
+                        for x in &mut naive[l..r] { *x = m.act(*x); }
+                        seg.range_update(l, r, m);
+                    },
+                    LazyOp::SetVal(i, x) => { naive[i] = x; seg.set_val(i, x); },
+                    LazyOp::Fold(l, r) => expect(seg.fold(l, r), fold_naive(&naive[l..r]), "fold")?,
+                    LazyOp::Get(i) => expect(seg.get(i), naive[i], "get")?,
+                    LazyOp::MaxRight(l, t) => {
+                        let want = (l..=n).rev().find(|&r| pred(&t, &fold_naive(&naive[l..r]))).unwrap();
+                        expect(seg.max_right(l, |x| pred(&t, x)), want, "max_right")?;
+                    },
+                    LazyOp::MinLeft(r, t) => {
+                        let want = (0..=r).find(|&l| pred(&t, &fold_naive(&naive[l..r]))).unwrap();
+                        expect(seg.min_left(r, |x| pred(&t, x)), want, "min_left")?;
+                    },
+                }
+            }
+            expect(seg.iter().copied().collect::<Vec<_>>(), naive.clone(), "iter")?;
+            expect(seg.into_vec(), naive, "into_vec")
+        },
+    );
+}
+
+#[test]
+fn lazy_seg_tree_add_min() {
+    check_lazy_seg_tree(
+        "lazy_seg_tree_add_min",
+        |rng| Min(rng.gen_range(-50..50)),
+        |rng| Add(rng.gen_range(-10..10)),
+        |t, x| x.0 >= t.0,
+    );
+}
+
+#[test]
+fn lazy_seg_tree_assign_sum() {
+    check_lazy_seg_tree(
+        "lazy_seg_tree_assign_sum",
+        |rng| Sum(rng.gen_range(0..50), 1),
+        |rng| Assign(Some(rng.gen_range(0..10))),
+        // sums are nonnegative, so the bound is monotone
+        |t, x| x.0 <= t.0 * 3,
+    );
+}
+
+const LO: i64 = -1_000_000_000_000_000_000;
+const HI: i64 = 1_000_000_000_000_000_000;
+
+// a few far-apart coordinates, so that updates and range ends collide
+fn coordinate(rng: &mut impl Rng, keys: &[i64]) -> i64 {
+    keys[rng.gen_range(0..keys.len())] + rng.gen_range(-1..=1)
+}
+
+fn affine_fold_naive(a: &BTreeMap<i64, Affine>, l: i64, r: i64) -> Affine {
+    a.range(l..r).fold(Affine::one(), |acc, (_, f)| acc.ope(f))
+}
+
+#[derive(Debug, Clone)]
+enum DynamicOp {
+    Update(i64, Affine),
+    Apply(i64, Affine),
+    Fold(i64, i64),
+}
+
+#[test]
+fn dynamic_seg_tree() {
+    check(
+        "dynamic_seg_tree",
+        |rng| {
+            let keys: Vec<i64> = (0..8).map(|_| rng.gen_range(LO + 1..HI - 1)).chain([LO + 1, HI - 2]).collect();
+            let ops = (0..100).map(|_| {
+                let f = Affine(rng.gen_range(0..MOD), rng.gen_range(0..MOD));
+                match rng.gen_range(0..3) {
+                    0 => DynamicOp::Update(coordinate(rng, &keys), f),
+                    1 => DynamicOp::Apply(coordinate(rng, &keys), f),
+                    _ => {
+                        let (a, b) = (coordinate(rng, &keys), coordinate(rng, &keys));
+                        DynamicOp::Fold(a.min(b), a.max(b))
+                    },
+                }
+            }).collect();
+            ((), ops)
+        },
+        |_, ops| {
+            let mut naive = BTreeMap::new();
+            let mut seg = DynamicSegTree::<Affine>::new(LO, HI);
+            for op in ops {
+                match *op {
+                    DynamicOp::Update(i, f) => { naive.insert(i, f); seg.update(i, f); },
+                    DynamicOp::Apply(i, f) => {
+                        let e = naive.entry(i).or_insert(Affine::one());
+                        *e = e.ope(&f);
+                        seg.apply(i, f);
+                    },
+                    DynamicOp::Fold(l, r) => {
+                        expect(seg.fold(l, r), affine_fold_naive(&naive, l, r), "fold")?;
+                        expect(seg.get(l), affine_fold_naive(&naive, l, l + 1), "get")?;
+                    },
+                }
+            }
+            expect(seg.fold(LO, HI), affine_fold_naive(&naive, LO, HI), "fold all")
+        },
+    );
+}
+
+// every operation picks an older version
+#[derive(Debug, Clone)]
+enum PersistentOp {
+    Update(usize, i64, Affine),
+    Apply(usize, i64, Affine),
+    Fold(usize, i64, i64),
+}
+
+#[test]
+fn persistent_seg_tree() {
+    check(
+        "persistent_seg_tree",
+        |rng| {
+            let keys: Vec<i64> = (0..8).map(|_| rng.gen_range(LO + 1..HI - 1)).collect();
+            let ops = (0..100).map(|t| {
+                let v = rng.gen_range(0..=t);
+                let f = Affine(rng.gen_range(0..MOD), rng.gen_range(0..MOD));
+                match rng.gen_range(0..3) {
+                    0 => PersistentOp::Update(v, coordinate(rng, &keys), f),
+                    1 => PersistentOp::Apply(v, coordinate(rng, &keys), f),
+                    _ => {
+                        let (a, b) = (coordinate(rng, &keys), coordinate(rng, &keys));
+                        PersistentOp::Fold(v, a.min(b), a.max(b))
+                    },
+                }
+            }).collect();
+            ((), ops)
+        },
+        |_, ops| {
+            let mut seg = PersistentSegTree::<Affine>::new(LO, HI);
+            // (root, naive) of each version, and every operation appends one
+            let mut versions = vec![(0, BTreeMap::new())];
+            for op in ops {
+                // versions may be missing after shrinking, so clamp to the latest one
+                let (v, next) = match *op {
+                    PersistentOp::Update(v, i, f) => {
+                        let v = v.min(versions.len() - 1);
+                        let mut naive = versions[v].1.clone();
+                        naive.insert(i, f);
+                        (v, (seg.update(versions[v].0, i, f), naive))
+                    },
+                    PersistentOp::Apply(v, i, f) => {
+                        let v = v.min(versions.len() - 1);
+                        let mut naive = versions[v].1.clone();
+                        let e = naive.entry(i).or_insert(Affine::one());
+                        *e = e.ope(&f);
+                        (v, (seg.apply(versions[v].0, i, f), naive))
+                    },
+                    PersistentOp::Fold(v, l, r) => {
+                        let v = v.min(versions.len() - 1);
+                        let (root, naive) = &versions[v];
+                        expect(seg.fold(*root, l, r), affine_fold_naive(naive, l, r), "fold")?;
+                        expect(seg.get(*root, l), affine_fold_naive(naive, l, l + 1), "get")?;
+                        (v, versions[v].clone())
+                    },
+                };
+                versions.push(next);
+                // the old version is untouched
+                let (root, naive) = &versions[v];
+                expect(seg.fold(*root, LO, HI), affine_fold_naive(naive, LO, HI), "old version")?;
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn persistent_seg_tree_kth_smallest() {
+    // the k-th smallest of xs[l..r] by counting trees over the values
+    let mut rng = <rand::rngs::StdRng as rand::SeedableRng>::seed_from_u64(0);
+    let xs: Vec<i64> = (0..200).map(|_| rng.gen_range(-1_000_000_000_000..1_000_000_000_000)).collect();
+    let mut seg = PersistentSegTree::<SumMonoid>::new(LO, HI);
+    let mut roots = vec![0];
+    for &x in &xs {
+        let root = seg.apply(*roots.last().unwrap(), x, SumMonoid(1));
+        roots.push(root);
+    }
+    for _ in 0..200 {
+        let l = rng.gen_range(0..xs.len());
+        let r = rng.gen_range(l + 1..=xs.len());
+        let k = rng.gen_range(0..r - l);
+        let count = |v: i64| seg.fold(roots[r], LO, v + 1).0 - seg.fold(roots[l], LO, v + 1).0;
+        // the smallest v with more than k values at most v
+        let (mut ng, mut ok) = (LO - 1, HI - 1);
+        while ok - ng > 1 {
+            let mid = ng + (ok - ng) / 2;
+            if count(mid) > k { ok = mid; } else { ng = mid; }
+        }
+        let mut sorted = xs[l..r].to_vec();
+        sorted.sort();
+        assert_eq!(ok, sorted[k]);
+    }
+}
+
+#[derive(Debug, Clone)]
+enum BeatsOp {
+    Chmin(usize, usize, i64),
+    Chmax(usize, usize, i64),
+    Add(usize, usize, i64),
+    Assign(usize, usize, i64),
+    Fold(usize, usize),
+}
+
+#[test]
+fn seg_tree_beats() {
+    check(
+        "seg_tree_beats",
+        |rng| {
+            let n = rng.gen_range(1..40);
+            let init: Vec<i64> = (0..n).map(|_| rng.gen_range(-50..50)).collect();
+            let ops = (0..100).map(|_| {
+                let (l, r) = range(rng, n);
+                let x = rng.gen_range(-50..50);
+                match rng.gen_range(0..5) {
+                    0 => BeatsOp::Chmin(l, r, x),
+                    1 => BeatsOp::Chmax(l, r, x),
+                    2 => BeatsOp::Add(l, r, x / 5),
+                    3 => BeatsOp::Assign(l, r, x),
+                    _ => BeatsOp::Fold(l, r),
+                }
+            }).collect();
+            (init, ops)
+        },
+        |init, ops| {
+            let mut naive = init.clone();
+            let mut seg = SegTreeBeats::from(init.clone());
+            for op in ops {
+                match *op {
+                    BeatsOp::Chmin(l, r, x) => { for a in &mut naive[l..r] { *a = (*a).min(x); } seg.chmin(l, r, x); },
+                    BeatsOp::Chmax(l, r, x) => { for a in &mut naive[l..r] { *a = (*a).max(x); } seg.chmax(l, r, x); },
+                    BeatsOp::Add(l, r, x) => { for a in &mut naive[l..r] { *a += x; } seg.add(l, r, x); },
+                    BeatsOp::Assign(l, r, x) => { for a in &mut naive[l..r] { *a = x; } seg.assign(l, r, x); },
+                    BeatsOp::Fold(l, r) => {
+                        let want = naive[l..r].iter().fold(SumMinMax::one(), |acc, &a| {
+                            acc.ope(&SumMinMax { sum: a, min: a, max: a })
+                        });
+                        expect(seg.fold(l, r), want, "fold")?;
+                    },
+                }
+            }
+            for (i, &a) in naive.iter().enumerate() {
+                expect(seg.get(i), a, "get")?;
+            }
+            Ok(())
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum BITOp {
+    Update(usize, i64),
+    Sum(usize, usize),
+    Get(usize),
+    LowerBound(i64),
+}
+
+#[test]
+fn bit() {
+    check(
+        "bit",
+        |rng| {
+            let n = rng.gen_range(1..40);
+            let init: Vec<i64> = (0..n).map(|_| rng.gen_range(0..5)).collect();
+            let ops = (0..100).map(|_| match rng.gen_range(0..4) {
+                0 => BITOp::Update(rng.gen_range(0..n), rng.gen_range(0..10)),
+                1 => { let (l, r) = range(rng, n); BITOp::Sum(l, r) },
+                2 => BITOp::Get(rng.gen_range(0..n)),
+                _ => BITOp::LowerBound(rng.gen_range(1..100)),
+            }).collect();
+            (init, ops)
+        },
+        |init, ops| {
+            let n = init.len();
+            let mut naive = init.clone();
+            let mut bit = BIT::from(init.clone());
+            for op in ops {
+                match *op {
+                    BITOp::Update(i, x) => { naive[i] += x; bit.update(i, x); },
+                    BITOp::Sum(l, r) => expect(bit.sum(l, r), naive[l..r].iter().sum(), "sum")?,
+                    BITOp::Get(i) => expect(bit.get(i), naive[i], "get")?,
+                    BITOp::LowerBound(w) => {
+                        // the first index whose prefix sum reaches w
+                        let mut acc = 0;
+                        let want = (0..n).find(|&i| { acc += naive[i]; acc >= w }).unwrap_or(n);
+                        expect(bit.lower_bound(w), want, "lower_bound")?;
+                    },
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum RangeBITOp {
+    Update(usize, usize, i64),
+    Sum(usize, usize),
+    Get(usize),
+}
+
+#[test]
+fn range_bit() {
+    check(
+        "range_bit",
+        |rng| {
+            let n = rng.gen_range(1..40);
+            let init: Vec<i64> = (0..n).map(|_| rng.gen_range(-10..10)).collect();
+            let ops = (0..100).map(|_| match rng.gen_range(0..3) {
+                0 => { let (l, r) = range(rng, n); RangeBITOp::Update(l, r, rng.gen_range(-10..10)) },
+                1 => { let (l, r) = range(rng, n); RangeBITOp::Sum(l, r) },
+                _ => RangeBITOp::Get(rng.gen_range(0..n)),
+            }).collect();
+            (init, ops)
+        },
+        |init, ops| {
+            let mut naive = init.clone();
+            let mut bit = RangeBIT::from(init.clone());
+            for op in ops {
+                match *op {
+                    RangeBITOp::Update(l, r, x) => { for a in &mut naive[l..r] { *a += x; } bit.update(l, r, x); },
+                    RangeBITOp::Sum(l, r) => expect(bit.sum(l, r), naive[l..r].iter().sum(), "sum")?,
+                    RangeBITOp::Get(i) => expect(bit.get(i), naive[i], "get")?,
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum CompressedBIT2DOp {
+    Update(usize, i64),
+    Sum(i64, i64, i64, i64),
+}
+
+#[test]
+fn compressed_bit_2d() {
+    check(
+        "compressed_bit_2d",
+        |rng| {
+            // few distinct coordinates, so that points share rows and columns
+            let cs: Vec<i64> = (0..6).map(|_| rng.gen_range(-1_000_000_000..=1_000_000_000)).collect();
+            let pick = |rng: &mut rand::rngs::StdRng| cs[rng.gen_range(0..cs.len())] + rng.gen_range(-1..=1);
+            let points: Vec<(i64, i64)> = (0..rng.gen_range(1..30)).map(|_| (pick(rng), pick(rng))).collect();
+            let ops = (0..100).map(|_| {
+                if rng.gen_bool(0.5) {
+                    CompressedBIT2DOp::Update(rng.gen_range(0..points.len()), rng.gen_range(-10..10))
+                } else {
+                    let (x0, x1, y0, y1) = (pick(rng), pick(rng), pick(rng), pick(rng));
+                    CompressedBIT2DOp::Sum(x0.min(x1), x0.max(x1), y0.min(y1), y0.max(y1))
+                }
+            }).collect();
+            (points, ops)
+        },
+        |points, ops| {
+            let mut naive = vec![0_i64; points.len()];
+            let mut bit = CompressedBIT2D::<i64>::new(points);
+            for op in ops {
+                match *op {
+                    CompressedBIT2DOp::Update(k, w) => { naive[k] += w; bit.update(points[k].0, points[k].1, w); },
+                    CompressedBIT2DOp::Sum(xl, xr, yl, yr) => {
+                        let want = points.iter().zip(&naive)
+                            .filter(|&(&(x, y), _)| xl <= x && x < xr && yl <= y && y < yr)
+                            .map(|(_, &w)| w)
+                            .sum();
+                        expect(bit.sum(xl, xr, yl, yr), want, "sum")?;
+                    },
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum BIT2DOp {
+    Update(usize, usize, i64),
+    Sum(usize, usize, usize, usize),
+}
+
+#[test]
+fn bit_2d() {
+    check(
+        "bit_2d",
+        |rng| {
+            let (h, w) = (rng.gen_range(1..10), rng.gen_range(1..10));
+            let ops = (0..100).map(|_| {
+                if rng.gen_bool(0.5) {
+                    BIT2DOp::Update(rng.gen_range(0..h), rng.gen_range(0..w), rng.gen_range(-10..10))
+                } else {
+                    let (li, ri) = range(rng, h);
+                    let (lj, rj) = range(rng, w);
+                    BIT2DOp::Sum(li, ri, lj, rj)
+                }
+            }).collect();
+            ((h, w), ops)
+        },
+        |&(h, w), ops| {
+            let mut naive = vec![vec![0_i64; w]; h];
+            let mut bit = BIT2D::<i64>::new(h, w);
+            for op in ops {
+                match *op {
+                    BIT2DOp::Update(i, j, x) => { naive[i][j] += x; bit.update(i, j, x); },
+                    BIT2DOp::Sum(li, ri, lj, rj) => {
+                        let want = naive[li..ri].iter().map(|row| row[lj..rj].iter().sum::<i64>()).sum();
+                        expect(bit.sum(li, ri, lj, rj), want, "sum")?;
+                    },
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn merge_sort_tree() {
+    check(
+        "merge_sort_tree",
+        |rng| {
+            let n = rng.gen_range(1..70);
+            let a: Vec<usize> = (0..n).map(|_| rng.gen_range(0..100)).collect();
+            let queries: Vec<(usize, usize, usize)> = (0..50).map(|_| {
+                let (l, r) = range(rng, n);
+                (l, r, rng.gen_range(0..110))
+            }).collect();
+            (a, queries)
+        },
+        |a, queries| {
+            let tree = MergeSortTree::new(a.clone());
+            for &(l, r, x) in queries {
+                let want = a[l..r].iter().filter(|&&y| y <= x).sum();
+                expect(tree.fold(l, r, x), want, "fold")?;
+            }
+            Ok(())
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum TrieOp {
+    Insert(usize),
+    Count(usize, usize),
+    Max(usize),
+    Min(usize),
+}
+
+#[test]
+fn binary_trie() {
+    const BITS: usize = 6;
+    check(
+        "binary_trie",
+        |rng| {
+            let first = rng.gen_range(0..1 << BITS);
+            let mut ops = vec![TrieOp::Insert(first)];
+            ops.extend((0..100).map(|_| {
+                let x = rng.gen_range(0..1 << BITS);
+                match rng.gen_range(0..4) {
+                    0 => TrieOp::Insert(x),
+                    1 => TrieOp::Count(x, rng.gen_range(0..1 << BITS)),
+                    2 => TrieOp::Max(x),
+                    _ => TrieOp::Min(x),
+                }
+            }));
+            ((), ops)
+        },
+        |_, ops| {
+            let mut naive: Vec<usize> = vec![];
+            let mut trie = BinaryTrie::new(BITS);
+            for op in ops {
+                match *op {
+                    TrieOp::Insert(x) => { naive.push(x); trie.insert(x); },
+                    TrieOp::Count(x, xor) => {
+                        expect(trie.count(x, xor), naive.iter().filter(|&&y| y == x ^ xor).count(), "count")?;
+                    },
+                    TrieOp::Max(xor) if !naive.is_empty() => {
+                        expect(trie.max(xor), naive.iter().map(|&y| y ^ xor).max().unwrap(), "max")?;
+                    },
+                    TrieOp::Min(xor) if !naive.is_empty() => {
+                        expect(trie.min(xor), naive.iter().map(|&y| y ^ xor).min().unwrap(), "min")?;
+                    },
+                    _ => {},
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum UFOp {
+    Union(usize, usize),
+    Same(usize, usize),
+    Size(usize),
+}
+
+#[test]
+fn union_find() {
+    check(
+        "union_find",
+        |rng| {
+            let n = rng.gen_range(1..30);
+            let ops = (0..100).map(|_| {
+                let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                match rng.gen_range(0..3) {
+                    0 => UFOp::Union(u, v),
+                    1 => UFOp::Same(u, v),
+                    _ => UFOp::Size(u),
+                }
+            }).collect();
+            (n, ops)
+        },
+        |&n, ops| {
+            let mut label: Vec<usize> = (0..n).collect();
+            let mut uf = UnionFind::new(n);
+            for op in ops {
+                match *op {
+                    UFOp::Union(u, v) => {
+                        expect(uf.union(u, v), label[u] != label[v], "union")?;
+                        let (a, b) = (label[u], label[v]);
+                        for x in &mut label { if *x == b { *x = a; } }
+                    },
+                    UFOp::Same(u, v) => expect(uf.same(u, v), label[u] == label[v], "same")?,
+                    UFOp::Size(u) => expect(uf.size(u), label.iter().filter(|&&x| x == label[u]).count(), "size")?,
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum RollbackUFOp {
+    Union(usize, usize),
+    Undo,
+    Snapshot,
+    Rollback,
+    Same(usize, usize),
+    Size(usize),
+}
+
+#[test]
+fn rollback_union_find() {
+    check(
+        "rollback_union_find",
+        |rng| {
+            let n = rng.gen_range(1..30);
+            let ops = (0..100).map(|_| {
+                let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                match rng.gen_range(0..8) {
+                    0..=2 => RollbackUFOp::Union(u, v),
+                    3 => RollbackUFOp::Undo,
+                    4 => RollbackUFOp::Snapshot,
+                    5 => RollbackUFOp::Rollback,
+                    6 => RollbackUFOp::Same(u, v),
+                    _ => RollbackUFOp::Size(u),
+                }
+            }).collect();
+            (n, ops)
+        },
+        |&n, ops| {
+            // the labels after each union, so undo pops one
+            let mut labels: Vec<Vec<usize>> = vec![(0..n).collect()];
+            let mut snap = 0;
+            let mut uf = RollbackUnionFind::new(n);
+            for op in ops {
+                let label = labels.last().unwrap().clone();
+                match *op {
+                    RollbackUFOp::Union(u, v) => {
+                        expect(uf.union(u, v), label[u] != label[v], "union")?;
+                        let (a, b) = (label[u], label[v]);
+                        labels.push(label.iter().map(|&x| if x == b { a } else { x }).collect());
+                    },
+                    RollbackUFOp::Undo if labels.len() > 1 => { labels.pop(); uf.undo(); },
+                    RollbackUFOp::Snapshot => { snap = labels.len(); uf.snapshot(); },
+                    RollbackUFOp::Rollback if snap <= labels.len() => { labels.truncate(snap.max(1)); uf.rollback(); },
+                    RollbackUFOp::Same(u, v) => expect(uf.same(u, v), label[u] == label[v], "same")?,
+                    RollbackUFOp::Size(u) => expect(uf.size(u), label.iter().filter(|&&x| x == label[u]).count(), "size")?,
+                    _ => {},
+                }
+                let label = labels.last().unwrap();
+                let mut roots = label.clone();
+                roots.sort();
+                roots.dedup();
+                expect(uf.count(), roots.len(), "count")?;
+                expect(uf.time(), labels.len() - 1, "time")?;
+            }
+            Ok(())
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum ConnectivityOp {
+    Add(usize, usize),
+    // removes the k-th alive edge
+    Remove(usize),
+    Query(usize, usize),
+}
+
+#[test]
+fn offline_dynamic_connectivity() {
+    check(
+        "offline_dynamic_connectivity",
+        |rng| {
+            let n = rng.gen_range(1..12);
+            let ops = (0..60).map(|_| {
+                let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                match rng.gen_range(0..3) {
+                    0 => ConnectivityOp::Add(u, v),
+                    1 => ConnectivityOp::Remove(rng.gen_range(0..100)),
+                    _ => ConnectivityOp::Query(u, v),
+                }
+            }).collect();
+            (n, ops)
+        },
+        |&n, ops| {
+            let mut alive: Vec<(usize, usize)> = vec![];
+            let mut want = vec![];
+            let mut dc = OfflineDynamicConnectivity::new(n);
+            for op in ops {
+                match *op {
+                    ConnectivityOp::Add(u, v) => { alive.push((u, v)); dc.add_edge(u, v); },
+                    ConnectivityOp::Remove(k) if !alive.is_empty() => {
+                        // the reversed endpoints are the same edge
+                        let (u, v) = alive.remove(k % alive.len());
+                        dc.remove_edge(v, u);
+                    },
+                    ConnectivityOp::Query(u, v) => {
+                        let mut label: Vec<usize> = (0..n).collect();
+                        for _ in 0..n {
+                            for &(a, b) in &alive {
+                                let m = label[a].min(label[b]);
+                                label[a] = m; label[b] = m;
+                            }
+                        }
+                        let count = (0..n).filter(|&x| label[x] == x).count();
+                        want.push((label[u] == label[v], count));
+                        dc.query(u, v);
+                    },
+                    _ => {},
+                }
+            }
+            expect(dc.solve(), want, "answers")
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum PUFOp<G> {
+    // potential(v) = potential(u) * w
+    Union(usize, usize, G),
+    // the same, but with the implied w when u and v are already connected
+    UnionImplied(usize, usize, G),
+    Diff(usize, usize),
+    Size(usize),
+}
+
+fn check_potential_union_find<G>(name: &str, gen_g: impl Fn(&mut rand::rngs::StdRng) -> G)
+where
+    G: Copy + PartialEq + Group + std::fmt::Debug,
+{
+    check(
+        name,
+        |rng| {
+            let n = rng.gen_range(1..30);
+            let ops = (0..100).map(|_| {
+                let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                match rng.gen_range(0..4) {
+                    0 => PUFOp::Union(u, v, gen_g(rng)),
+                    1 => PUFOp::UnionImplied(u, v, gen_g(rng)),
+                    2 => PUFOp::Diff(u, v),
+                    _ => PUFOp::Size(u),
+                }
+            }).collect();
+            (n, ops)
+        },
+        |&n, ops| {
+            // each vertex has a label of its component and an absolute value
+            let mut label: Vec<usize> = (0..n).collect();
+            let mut value = vec![G::one(); n];
+            let mut uf = PotentialUnionFind::<G>::new(n);
+            for op in ops {
+                match *op {
+                    PUFOp::Union(u, v, w) | PUFOp::UnionImplied(u, v, w) => {
+                        let existing = value[u].inv().ope(&value[v]);
+                        let w = match op {
+                            PUFOp::UnionImplied(..) if label[u] == label[v] => existing,
+                            _ => w,
+                        };
+                        let want = if label[u] != label[v] {
+                            Ok(true)
+                        } else if existing == w {
+                            Ok(false)
+                        } else {
+                            Err(Contradiction { u, v, given: w, existing })
+                        };
+                        expect(uf.union(u, v, w), want, "union")?;
+                        if label[u] == label[v] { continue; }
+                        // multiply the component of v from the left, which keeps its differences
+                        let (a, b) = (label[u], label[v]);
+                        let shift = value[u].ope(&w).ope(&value[v].inv());
+                        for x in 0..n {
+                            if label[x] == b { label[x] = a; value[x] = shift.ope(&value[x]); }
+                        }
+                    },
+                    PUFOp::Diff(u, v) => {
+                        let want = (label[u] == label[v]).then(|| value[u].inv().ope(&value[v]));
+                        expect(uf.diff(u, v), want, "diff")?;
+                    },
+                    PUFOp::Size(u) => expect(uf.size(u), label.iter().filter(|&&x| x == label[u]).count(), "size")?,
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn potential_union_find() {
+    check_potential_union_find::<i64>("potential_union_find", |rng| rng.gen_range(-10..10));
+}
+
+// parity constraints, i.e. bipartiteness
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Xor(u8);
+impl Monoid for Xor {
+    fn one() -> Self { Xor(0) }
+    fn ope(&self, rhs: &Self) -> Self { Xor(self.0 ^ rhs.0) }
+}
+impl Group for Xor {
+    fn inv(self) -> Self { self }
+}
+
+#[test]
+fn potential_union_find_xor() {
+    check_potential_union_find("potential_union_find_xor", |rng| Xor(rng.gen_range(0..2)));
+}
+
+fn pow_mod(mut a: u64, mut e: u64) -> u64 {
+    let mut ret = 1;
+    while e > 0 {
+        if e & 1 == 1 { ret = ret * a % MOD; }
+        a = a * a % MOD;
+        e >>= 1;
+    }
+    ret
+}
+
+// x -> a * x + b is invertible when a != 0
+impl Group for Affine {
+    fn inv(self) -> Self {
+        let a = pow_mod(self.0, MOD - 2);
+        Affine(a, (MOD - self.1) * a % MOD)
+    }
+}
+
+#[test]
+fn potential_union_find_affine() {
+    // small values, so that the implied constraints repeat
+    check_potential_union_find("potential_union_find_affine", |rng| Affine(rng.gen_range(1..4), rng.gen_range(0..3)));
+}
+
+#[derive(Debug, Clone)]
+enum MultiSetOp {
+    Add(i32),
+    Remove(i32),
+    RemoveAll(i32),
+    LowerBound(i32),
+}
+
+#[test]
+fn multiset() {
+    check(
+        "multiset",
+        |rng| {
+            let ops = (0..100).map(|_| {
+                let x = rng.gen_range(0..20);
+                match rng.gen_range(0..5) {
+                    0 | 1 => MultiSetOp::Add(x),
+                    2 => MultiSetOp::Remove(x),
+                    3 => MultiSetOp::RemoveAll(x),
+                    _ => MultiSetOp::LowerBound(x),
+                }
+            }).collect();
+            ((), ops)
+        },
+        |_, ops| {
+            let mut naive: BTreeMap<i32, usize> = BTreeMap::new();
+            let mut set = MultiSet::new();
+            for op in ops {
+                match *op {
+                    MultiSetOp::Add(x) => { *naive.entry(x).or_insert(0) += 1; set.add(x); },
+                    MultiSetOp::Remove(x) if naive.contains_key(&x) => {
+                        *naive.get_mut(&x).unwrap() -= 1;
+                        if naive[&x] == 0 { naive.remove(&x); }
+                        set.remove(x);
+                    },
+                    MultiSetOp::RemoveAll(x) => { naive.remove(&x); set.remove_all(x); },
+                    MultiSetOp::LowerBound(x) => expect(set.lower_bound(x), naive.range(x..).next(), "lower_bound")?,
+                    _ => {},
+                }
+                expect(set.min(), naive.keys().next(), "min")?;
+                expect(set.max(), naive.keys().last(), "max")?;
+                expect(set.keys().collect::<Vec<_>>(), naive.keys().collect(), "keys")?;
+            }
+            Ok(())
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum CounterOp {
+    Increment(u8),
+    Decrement(u8),
+    Count(u8),
+}
+
+#[test]
+fn counter() {
+    check(
+        "counter",
+        |rng| {
+            let ops = (0..100).map(|_| {
+                let x = rng.gen_range(0..10);
+                match rng.gen_range(0..3) {
+                    0 => CounterOp::Increment(x),
+                    1 => CounterOp::Decrement(x),
+                    _ => CounterOp::Count(x),
+                }
+            }).collect();
+            ((), ops)
+        },
+        |_, ops| {
+            let mut naive: BTreeMap<u8, usize> = BTreeMap::new();
+            let mut counter = Counter::new();
+            for op in ops {
+                match *op {
+                    CounterOp::Increment(x) => { *naive.entry(x).or_insert(0) += 1; counter.increment(x); },
+                    CounterOp::Decrement(x) if naive.contains_key(&x) => {
+                        *naive.get_mut(&x).unwrap() -= 1;
+                        if naive[&x] == 0 { naive.remove(&x); }
+                        counter.decrement(x);
+                    },
+                    CounterOp::Count(x) => expect(counter.count(x), naive.get(&x).copied().unwrap_or(0), "count")?,
+                    _ => {},
+                }
+                expect(counter.iter().collect::<Vec<_>>(), naive.iter().collect(), "iter")?;
+            }
+            Ok(())
+        },
+    );
+}
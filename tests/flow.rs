@@ -0,0 +1,326 @@
+#![allow(non_snake_case)]
+
+mod common;
+
+use algorithms::algebra::MinMonoid;
+use algorithms::data_structure::seg_tree::SegTree;
+use algorithms::flow::lower_bound_flow::LowerBoundFlow;
+use algorithms::flow::maxflow::FlowGraph;
+use algorithms::flow::min_cost_flow::FlowGraph as CostFlowGraph;
+use algorithms::flow::min_cut::{all_pairs_min_cut, gomory_hu, stoer_wagner};
+use algorithms::graph::directed_tree::DirectedTree;
+use algorithms::graph::hld::HLD;
+use algorithms::graph::Graph;
+use common::{check, expect};
+use rand::Rng;
+
+// the capacity of the cut for each subset S of the vertices, as a bitmask
+fn cut_value(edges: &[(usize, usize, i64)], S: usize) -> i64 {
+    edges.iter().filter(|&&(u, v, _)| S >> u & 1 == 1 && S >> v & 1 == 0).map(|e| e.2).sum()
+}
+
+// the minimum cut over every subset with s and without t
+fn min_cut_naive(n: usize, s: usize, t: usize, edges: &[(usize, usize, i64)]) -> i64 {
+    (0..1 << n).filter(|&S| S >> s & 1 == 1 && S >> t & 1 == 0).map(|S| cut_value(edges, S)).min().unwrap()
+}
+
+// (n, source, sink) and the edges (from, to, cap)
+type Network = ((usize, usize, usize), Vec<(usize, usize, i64)>);
+
+fn random_network(rng: &mut impl Rng) -> Network {
+    let n = rng.gen_range(2..8);
+    let s = rng.gen_range(0..n);
+    let t = (s + rng.gen_range(1..n)) % n;
+    let m = rng.gen_range(0..3 * n);
+    let edges = (0..m).map(|_| {
+        let u = rng.gen_range(0..n);
+        (u, (u + rng.gen_range(1..n)) % n, rng.gen_range(0..10))
+    }).collect();
+    ((n, s, t), edges)
+}
+
+// the flow is within the capacities and conserved but at s and t, and returns the amount out of s
+fn check_flow(n: usize, s: usize, t: usize, flows: &[(usize, usize, i64, i64)]) -> Result<i64, String> {
+    let mut excess = vec![0; n];
+    for &(u, v, cap, flow) in flows {
+        expect(0 <= flow && flow <= cap, true, "capacity")?;
+        excess[u] -= flow;
+        excess[v] += flow;
+    }
+    for v in (0..n).filter(|&v| v != s && v != t) {
+        expect(excess[v], 0, "conservation")?;
+    }
+    Ok(-excess[s])
+}
+
+#[test]
+fn maxflow_cut_and_decomposition() {
+    check(
+        "maxflow",
+        random_network,
+        |&(n, s, t), edges| {
+            let want = min_cut_naive(n, s, t, edges);
+            let mut fg = FlowGraph::new(n);
+            for (i, &(u, v, cap)) in edges.iter().enumerate() {
+                expect(fg.add_edge(u, v, cap), i, "index")?;
+            }
+            expect(fg.flow(s, t), want, "flow")?;
+            let flows = fg.edges();
+            expect(flows.iter().map(|e| (e.0, e.1, e.2)).collect::<Vec<_>>(), edges.to_vec(), "edges")?;
+            expect(check_flow(n, s, t, &flows)?, want, "flow out of s")?;
+
+            let side = fg.min_cut(s);
+            expect((side[s], side[t]), (true, false), "sides")?;
+            let S = (0..n).filter(|&v| side[v]).map(|v| 1 << v).sum();
+            expect(cut_value(edges, S), want, "cut")?;
+
+            let paths = fg.decompose(s, t);
+            expect(paths.iter().map(|p| p.0).sum::<i64>(), want, "decomposed")?;
+            let mut used = vec![vec![0; n]; n];
+            for (c, path) in &paths {
+                expect(*c > 0, true, "positive")?;
+                expect((path[0], *path.last().unwrap()), (s, t), "path ends")?;
+                for p in path.windows(2) {
+                    used[p[0]][p[1]] += c;
+                }
+            }
+            for (u, row) in used.iter().enumerate() {
+                for (v, &x) in row.iter().enumerate() {
+                    let total: i64 = flows.iter().filter(|e| (e.0, e.1) == (u, v)).map(|e| e.3).sum();
+                    expect(x <= total, true, "within the flow")?;
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn maxflow_limit_and_change_edge() {
+    check(
+        "maxflow_limit",
+        random_network,
+        |&(n, s, t), edges| {
+            let want = min_cut_naive(n, s, t, edges);
+            let mut fg = FlowGraph::new(n);
+            for &(u, v, cap) in edges {
+                fg.add_edge(u, v, cap);
+            }
+            let limit = want / 2 + 1;
+            let first = fg.flow_with_limit(s, t, limit);
+            expect(first, want.min(limit), "limited")?;
+            check_flow(n, s, t, &fg.edges())?;
+            expect(first + fg.flow(s, t), want, "resumed")?;
+
+            // double every capacity and start over
+            let doubled: Vec<_> = edges.iter().map(|&(u, v, cap)| (u, v, 2 * cap)).collect();
+            for (i, &(_, _, cap)) in doubled.iter().enumerate() {
+                fg.change_edge(i, cap, 0);
+            }
+            expect(fg.flow(s, t), 2 * want, "changed")
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "FLOW NOT CONSERVED")]
+fn decompose_needs_conservation() {
+    let mut fg = FlowGraph::new(3);
+    fg.add_edge(0, 1, 2);
+    let i = fg.add_edge(1, 2, 2);
+    fg.flow(0, 2);
+    fg.change_edge(i, 2, 1);
+    fg.decompose(0, 2);
+}
+
+// every flow within the bounds, as the flow on each edge
+fn all_flows(bounds: &[(usize, usize, i64, i64)]) -> Vec<Vec<i64>> {
+    let mut flows = vec![vec![]];
+    for &(_, _, lower, upper) in bounds {
+        flows = flows.into_iter().flat_map(|f: Vec<i64>| (lower..=upper).map(move |x| {
+            let mut f = f.clone();
+            f.push(x);
+            f
+        })).collect();
+    }
+    flows
+}
+
+// the net flow out of each vertex
+fn net_out(n: usize, bounds: &[(usize, usize, i64, i64)], flow: &[i64]) -> Vec<i64> {
+    let mut out = vec![0; n];
+    for (&(u, v, _, _), &x) in bounds.iter().zip(flow) {
+        out[u] += x;
+        out[v] -= x;
+    }
+    out
+}
+
+#[test]
+fn lower_bound_flow_matches_naive() {
+    check(
+        "lower_bound_flow",
+        |rng| {
+            let n = rng.gen_range(2..6);
+            let s = rng.gen_range(0..n);
+            let t = (s + rng.gen_range(1..n)) % n;
+            let m = rng.gen_range(0..6);
+            let bounds = (0..m).map(|_| {
+                let u = rng.gen_range(0..n);
+                let lower = rng.gen_range(0..3);
+                (u, (u + rng.gen_range(1..n)) % n, lower, lower + rng.gen_range(0..3))
+            }).collect();
+            ((n, s, t), bounds)
+        },
+        |&(n, s, t), bounds| {
+            let mut lbf = LowerBoundFlow::new(n);
+            for &(u, v, lower, upper) in bounds {
+                lbf.add_edge(u, v, lower, upper);
+            }
+            let flows = all_flows(bounds);
+            let within = |f: &[i64]| f.iter().zip(bounds).all(|(&x, b)| b.2 <= x && x <= b.3);
+
+            let circulation = flows.iter().any(|f| net_out(n, bounds, f).iter().all(|&x| x == 0));
+            match lbf.circulation() {
+                None => expect(circulation, false, "no circulation")?,
+                Some(f) => {
+                    expect(within(&f), true, "circulation bounds")?;
+                    expect(net_out(n, bounds, &f).iter().all(|&x| x == 0), true, "circulation")?;
+                },
+            }
+
+            // the values of the feasible flows from s to t
+            let values: Vec<i64> = flows.iter().filter_map(|f| {
+                let out = net_out(n, bounds, f);
+                (0..n).all(|v| v == s || v == t || out[v] == 0).then_some(out[s])
+            }).filter(|&x| x >= 0).collect();
+            for (got, want) in [(lbf.max_flow(s, t), values.iter().max()), (lbf.min_flow(s, t), values.iter().min())] {
+                expect(got.as_ref().map(|g| g.0), want.copied(), "value")?;
+                if let Some((value, f)) = got {
+                    expect(within(&f), true, "bounds")?;
+                    let out = net_out(n, bounds, &f);
+                    expect((0..n).all(|v| v == s || v == t || out[v] == 0), true, "conservation")?;
+                    expect(out[s], value, "flow out of s")?;
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn min_cost_flow_matches_naive() {
+    check(
+        "min_cost_flow",
+        |rng| {
+            let n = rng.gen_range(2..6);
+            let s = rng.gen_range(0..n);
+            let t = (s + rng.gen_range(1..n)) % n;
+            let m = rng.gen_range(0..6);
+            let edges = (0..m).map(|_| {
+                let u = rng.gen_range(0..n);
+                let v = (u + rng.gen_range(1..n)) % n;
+                (u, v, rng.gen_range(0..4), if u < v { rng.gen_range(-5..10) } else { rng.gen_range(0..10) })
+            }).collect();
+            ((n, s, t), edges)
+        },
+        |&(n, s, t), edges: &[(usize, usize, i64, i64)]| {
+            let build = || {
+                let mut fg = CostFlowGraph::new(n);
+                for &(u, v, cap, cost) in edges {
+                    fg.add_edge(u, v, cap, cost);
+                }
+                fg
+            };
+            let bounds: Vec<_> = edges.iter().map(|&(u, v, cap, _)| (u, v, 0, cap)).collect();
+            let cost_of = |f: &[i64]| f.iter().zip(edges).map(|(&x, e)| x * e.3).sum::<i64>();
+            // the minimum cost for each value of the flow from s to t, and of a circulation
+            let mut best: Vec<Option<i64>> = vec![None; 4 * edges.len() + 1];
+            let mut circulation = 0;
+            for f in all_flows(&bounds) {
+                let out = net_out(n, &bounds, &f);
+                if !(0..n).all(|v| v == s || v == t || out[v] == 0) || out[s] < 0 { continue; }
+                let c = cost_of(&f);
+                let b = &mut best[out[s] as usize];
+                *b = Some(b.map_or(c, |b: i64| b.min(c)));
+                if out[s] == 0 { circulation = circulation.min(c); }
+            }
+
+            let mut fg = build();
+            expect(fg.min_cost_circulation(), circulation, "circulation")?;
+            let flows = fg.edges();
+            let f: Vec<i64> = flows.iter().map(|e| e.3).collect();
+            expect(net_out(n, &bounds, &f).iter().all(|&x| x == 0), true, "conserved")?;
+            expect(cost_of(&f), circulation, "circulation cost")?;
+            // a negative cycle is not allowed for slope
+            if circulation < 0 { return Ok(()); }
+
+            let max_flow = best.iter().rposition(|b| b.is_some()).unwrap() as i64;
+            let mut fg = build();
+            let slope = fg.slope(s, t, i64::MAX);
+            expect(slope[0], (0, 0), "origin")?;
+            expect(slope.last().unwrap().0, max_flow, "max flow")?;
+            for w in slope.windows(3) {
+                // strictly convex at each breakpoint
+                expect((w[1].1 - w[0].1) * (w[2].0 - w[1].0) < (w[2].1 - w[1].1) * (w[1].0 - w[0].0), true, "convex")?;
+            }
+            for w in slope.windows(2) {
+                for x in w[0].0..=w[1].0 {
+                    let y = w[0].1 + (w[1].1 - w[0].1) * (x - w[0].0) / (w[1].0 - w[0].0);
+                    expect(Some(y), best[x as usize], "slope")?;
+                }
+            }
+            let f: Vec<i64> = fg.edges().iter().map(|e| e.3).collect();
+            expect(net_out(n, &bounds, &f)[s], max_flow, "edges flow")?;
+            expect(cost_of(&f), slope.last().unwrap().1, "edges cost")?;
+
+            for x in 0..=max_flow + 1 {
+                expect(build().min_cost(s, t, x), best.get(x as usize).copied().flatten(), "min_cost")?;
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn min_cut_matches_naive() {
+    check(
+        "min_cut",
+        |rng| {
+            let n = rng.gen_range(2..8);
+            let m = rng.gen_range(0..3 * n);
+            (n, (0..m).map(|_| (rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(0..10))).collect())
+        },
+        |&n, edges: &[(usize, usize, i64)]| {
+            let G = Graph::from_undirected_edges(n, edges);
+            // each undirected edge counts once in either direction
+            let both: Vec<_> = edges.iter().flat_map(|&(u, v, c)| [(u, v, c), (v, u, c)]).collect();
+            let (value, side) = stoer_wagner(&G);
+            let want = (1..(1 << n) - 1).map(|S| cut_value(&both, S)).min().unwrap();
+            expect(value, want, "global")?;
+            let S = (0..n).filter(|&v| side[v]).map(|v| 1 << v).sum::<usize>();
+            expect((0 < S && S < (1 << n) - 1, cut_value(&both, S)), (true, want), "side")?;
+
+            let tree = gomory_hu(&G);
+            expect(tree.edges().count(), 2 * (n - 1), "tree edges")?;
+            let cut = all_pairs_min_cut(&tree);
+            let rooted = DirectedTree::from_graph(0, &tree);
+            let hld = HLD::from_weighted(&rooted);
+            let seg = SegTree::from(hld.edge_seq(&rooted, i64::MAX).iter().map(|&c| MinMonoid(c as usize)).collect());
+            // removing the edge to the parent of v leaves the subtree of v as one side of the cut
+            for v in 1..n {
+                let (l, r) = hld.subtree_interval(v);
+                let S = hld.seq[l..r].iter().map(|&x| 1 << x).sum::<usize>();
+                expect(cut_value(&both, S), rooted.parent(v).unwrap().1, "tree edge cut")?;
+            }
+            for (s, row) in cut.iter().enumerate() {
+                for t in (0..n).filter(|&t| t != s) {
+                    let want = min_cut_naive(n, s, t, &both);
+                    expect(row[t], want, "all pairs")?;
+                    expect(hld.path_fold_commutative(&seg, s, t, true).0 as i64, want, "path min")?;
+                }
+            }
+            Ok(())
+        },
+    );
+}
@@ -0,0 +1,1025 @@
+#![allow(non_snake_case)]
+
+mod common;
+
+use algorithms::algebra::*;
+use algorithms::data_structure::seg_tree::SegTree;
+use algorithms::flow::maxflow::FlowGraph;
+use algorithms::flow::min_cost_flow::FlowGraph as CostFlowGraph;
+use algorithms::graph::diameter::Diameter;
+use algorithms::graph::directed_tree::{DirectedTree, DirectedTreeNorm};
+use algorithms::graph::hld::{PathLazySegTree, PathSegTree, HLD};
+use algorithms::graph::lca::LCA;
+use algorithms::graph::link_cut_tree::LinkCutTree;
+use algorithms::graph::lowlink::LowLink;
+use algorithms::graph::rerooting::rerooting;
+use algorithms::graph::matching::{hungarian, max_matching, BipartiteMatching};
+use algorithms::graph::reachability::Reachability;
+use algorithms::graph::scc::SCC;
+use algorithms::graph::shortest_path::{bellman_ford, bfs01, dijkstra, negative_cycle, warshall_floyd, ShortestPath};
+use algorithms::graph::topological_sort::topological_sort;
+use algorithms::graph::two_sat::TwoSAT;
+use algorithms::graph::{Adjacency, CsrGraph, Graph, Weight};
+use common::{check, expect};
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+const INF: i64 = i64::MAX / 4;
+
+// distances by relaxing every edge n times from each source, and whether each pair is unbounded below
+// through a vertex on a negative closed walk
+fn shortest_naive(n: usize, edges: &[(usize, usize, i64)]) -> (Vec<Vec<i64>>, Vec<Vec<bool>>) {
+    let relax = |d: &mut Vec<i64>| {
+        for _ in 0..n {
+            for &(u, v, w) in edges {
+                if d[u] < INF && d[u] + w < d[v] { d[v] = d[u] + w; }
+            }
+        }
+    };
+    let mut dist = vec![vec![INF; n]; n];
+    let mut reach = vec![vec![false; n]; n];
+    let mut on_cycle = vec![false; n];
+    for s in 0..n {
+        dist[s][s] = 0;
+        relax(&mut dist[s]);
+        reach[s] = dist[s].iter().map(|&d| d < INF).collect();
+        // the shortest closed walk of at least one edge
+        let mut d = vec![INF; n];
+        for &(u, v, w) in edges {
+            if u == s { d[v] = d[v].min(w); }
+        }
+        relax(&mut d);
+        on_cycle[s] = d[s] < 0;
+    }
+    let negative = (0..n).map(|s| (0..n).map(|v| (0..n).any(|c| reach[s][c] && on_cycle[c] && reach[c][v])).collect()).collect();
+    (dist, negative)
+}
+
+fn random_edges(rng: &mut impl Rng, n: usize, min_w: i64, max_w: i64) -> Vec<(usize, usize, i64)> {
+    let m = rng.gen_range(0..3 * n);
+    (0..m).map(|_| (rng.gen_range(0..n), rng.gen_range(0..n), rng.gen_range(min_w..=max_w))).collect()
+}
+
+// the weight of a walk taking the lightest edge each step, or None if some edge is missing
+fn walk_weight(edges: &[(usize, usize, i64)], walk: &[usize]) -> Option<i64> {
+    walk.windows(2).map(|p| edges.iter().filter(|e| (e.0, e.1) == (p[0], p[1])).map(|e| e.2).min()).sum()
+}
+
+fn check_single_source(
+    name: &str,
+    min_w: i64,
+    max_w: i64,
+    solve: impl Fn(&Graph<i64>, usize) -> (ShortestPath<i64>, Vec<bool>),
+) {
+    check(
+        name,
+        |rng| {
+            let n = rng.gen_range(1..12);
+            (n, random_edges(rng, n, min_w, max_w))
+        },
+        |&n, edges| {
+            let G = Graph::from_edges(n, edges);
+            let (dist, negative) = shortest_naive(n, edges);
+            for s in 0..n {
+                let (sp, neg) = solve(&G, s);
+                expect(&neg, &negative[s], "negative")?;
+                // the zero of AGroup, with both the algebra traits and Weight in scope
+                if !neg[s] { expect(sp.dist[s], i64::zero(), "source")?; }
+                for v in (0..n).filter(|&v| !neg[v]) {
+                    expect(sp.dist[v], dist[s][v], "dist")?;
+                    let path = sp.path_to(v);
+                    expect(path.is_some(), dist[s][v] < i64::inf(), "reachable")?;
+                    if let Some(path) = path {
+                        expect((path[0], *path.last().unwrap()), (s, v), "path ends")?;
+                        expect(walk_weight(edges, &path), Some(dist[s][v]), "path weight")?;
+                    }
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn dijkstra_matches_naive() {
+    check_single_source("dijkstra", 0, 20, |G, s| (dijkstra(G, s), vec![false; G.len()]));
+}
+
+#[test]
+fn bfs01_matches_naive() {
+    check_single_source("bfs01", 0, 1, |G, s| (bfs01(G, s), vec![false; G.len()]));
+}
+
+#[test]
+fn bellman_ford_matches_naive() {
+    check_single_source("bellman_ford", -5, 20, bellman_ford);
+}
+
+#[test]
+fn warshall_floyd_matches_naive() {
+    check(
+        "warshall_floyd",
+        |rng| {
+            let n = rng.gen_range(1..12);
+            let min_w = if rng.gen_bool(0.5) { 0 } else { -5 };
+            (n, random_edges(rng, n, min_w, 20))
+        },
+        |&n, edges| {
+            let (dist, negative) = warshall_floyd(&Graph::from_edges(n, edges));
+            let want = shortest_naive(n, edges);
+            expect(&negative, &want.1, "negative")?;
+            for u in 0..n {
+                for v in (0..n).filter(|&v| !negative[u][v]) {
+                    expect(dist[u][v], want.0[u][v], "dist")?;
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[test]
+fn negative_cycle_is_valid() {
+    check(
+        "negative_cycle",
+        |rng| {
+            let n = rng.gen_range(1..12);
+            (n, random_edges(rng, n, -5, 20))
+        },
+        |&n, edges| {
+            let exists = shortest_naive(n, edges).1.iter().any(|row| row.iter().any(|&b| b));
+            match negative_cycle(&Graph::from_edges(n, edges)) {
+                None => expect(exists, false, "exists"),
+                Some(mut cycle) => {
+                    cycle.push(cycle[0]);
+                    let w = walk_weight(edges, &cycle).ok_or("not a cycle")?;
+                    expect(w < 0, true, "negative")
+                },
+            }
+        },
+    );
+}
+
+#[test]
+fn graph_backends_agree() {
+    check(
+        "graph_backends",
+        |rng| {
+            let n = rng.gen_range(1..12);
+            (n, random_edges(rng, n, 0, 20))
+        },
+        |&n, edges| {
+            let G = Graph::from_edges(n, edges);
+            let csr = CsrGraph::from_edges(n, edges);
+            expect(csr.len(), n, "len")?;
+            for u in 0..n {
+                expect(csr.neighbors(u), G.neighbors(u), "neighbors")?;
+                expect(CsrGraph::from_graph(&G).neighbors(u), G.neighbors(u), "from_graph")?;
+            }
+            for s in 0..n {
+                expect(dijkstra(&csr, s).dist, dijkstra(&G, s).dist, "dijkstra")?;
+            }
+            let E: Vec<_> = edges.iter().map(|&(u, v, _)| (u, v)).collect();
+            expect(SCC::from_graph(&csr).cmp(), SCC::from(n, E).cmp(), "scc")?;
+
+            let lists = (0..n).map(|u| edges.iter().filter(|e| e.0 == u).map(|e| e.1).collect()).collect();
+            let want = LowLink::new(&lists);
+            let got = LowLink::from_graph(&csr);
+            expect((&got.ord, &got.low, &got.aps, &got.bridges), (&want.ord, &want.low, &want.aps, &want.bridges), "lowlink")
+        },
+    );
+}
+
+#[test]
+fn tree_modules_accept_graph() {
+    check(
+        "tree_graph",
+        |rng| {
+            let n = rng.gen_range(1..30);
+            let mut tree = random_tree(rng, n);
+            tree.shuffle(rng);
+            (n, tree)
+        },
+        |&n, tree| {
+            let E: Vec<_> = tree.iter().map(|&(u, v)| (u, v, u as i64 * 7 % 5)).collect();
+            let G = Graph::from_undirected_edges(n, &E);
+            let csr = CsrGraph::from_undirected_edges(n, &E);
+            let mut lists = vec![vec![]; n];
+            for &(u, v) in tree {
+                lists[u].push(v);
+                lists[v].push(u);
+            }
+            let root = n / 2;
+
+            let want = HLD::new(root, n, &tree.to_vec());
+            expect(&HLD::from_graph(root, &G).seq, &want.seq, "hld")?;
+            expect(&HLD::from_graph(root, &csr).seq, &want.seq, "hld csr")?;
+            let weighted = DirectedTree::from_graph(root, &csr);
+            for v in 0..n {
+                expect(weighted.parent(v), DirectedTree::new(root, n, &E).parent(v), "parent")?;
+            }
+
+            let want = LCA::new(n, root, lists.clone(), 5);
+            let got = LCA::from_graph(&csr, root, 5);
+            for u in 0..n {
+                for v in 0..n {
+                    expect(got.lca(u, v), want.lca(u, v), "lca")?;
+                }
+            }
+
+            let want = Diameter::new(&lists);
+            let got = Diameter::from_graph(&G);
+            expect((got.D, got.left, got.right), (want.D, want.left, want.right), "diameter")
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum Constraint {
+    Clause(usize, bool, usize, bool),
+    Implies(usize, bool, usize, bool),
+    Equal(usize, usize),
+    Xor(usize, usize),
+    Fix(usize, bool),
+    AtMostOne(Vec<(usize, bool)>),
+}
+
+impl Constraint {
+    fn holds(&self, a: &[bool]) -> bool {
+        match *self {
+            Constraint::Clause(x, f, y, g) => a[x] == f || a[y] == g,
+            Constraint::Implies(x, f, y, g) => a[x] != f || a[y] == g,
+            Constraint::Equal(x, y) => a[x] == a[y],
+            Constraint::Xor(x, y) => a[x] != a[y],
+            Constraint::Fix(x, f) => a[x] == f,
+            Constraint::AtMostOne(ref ls) => ls.iter().filter(|&&(x, f)| a[x] == f).count() <= 1,
+        }
+    }
+}
+
+#[test]
+fn two_sat_matches_naive() {
+    check(
+        "two_sat",
+        |rng| {
+            let n = rng.gen_range(1..7);
+            let m = rng.gen_range(0..2 * n);
+            let ops = (0..m).map(|_| {
+                let (x, y) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                match rng.gen_range(0..6) {
+                    0 => Constraint::Clause(x, rng.gen(), y, rng.gen()),
+                    1 => Constraint::Implies(x, rng.gen(), y, rng.gen()),
+                    2 => Constraint::Equal(x, y),
+                    3 => Constraint::Xor(x, y),
+                    4 => Constraint::Fix(x, rng.gen()),
+                    _ => Constraint::AtMostOne((0..rng.gen_range(0..5)).map(|_| (rng.gen_range(0..n), rng.gen())).collect()),
+                }
+            }).collect();
+            (n, ops)
+        },
+        |&n, ops: &[Constraint]| {
+            let holds = |a: &[bool]| ops.iter().all(|c| c.holds(a));
+            let exists = (0..1 << n).any(|bits: usize| holds(&(0..n).map(|i| bits >> i & 1 == 1).collect::<Vec<_>>()));
+            let mut ts = TwoSAT::new(n);
+            for c in ops {
+                match *c {
+                    Constraint::Clause(x, f, y, g) => ts.add_clause(x, f, y, g),
+                    Constraint::Implies(x, f, y, g) => ts.implies(x, f, y, g),
+                    Constraint::Equal(x, y) => ts.equal(x, y),
+                    Constraint::Xor(x, y) => ts.xor(x, y),
+                    Constraint::Fix(x, f) => ts.fix(x, f),
+                    Constraint::AtMostOne(ref ls) => ts.at_most_one(ls),
+                }
+            }
+            if ops.iter().all(|c| matches!(c, Constraint::Clause(..))) {
+                expect(ts.implication_graph().edges().count(), 2 * ops.len(), "implications")?;
+            }
+            let mut copy = TwoSAT::from_graph(&CsrGraph::from_graph(ts.implication_graph()));
+            expect(copy.satisfiable(), exists, "satisfiable from the graph")?;
+            expect(ts.satisfiable(), exists, "satisfiable")?;
+            if exists {
+                expect(&copy.answer()[..n], &ts.answer()[..], "answer from the graph")?;
+                expect(ts.answer().len(), n, "answer length")?;
+                expect(holds(ts.answer()), true, "answer")?;
+                return expect(ts.explain(), None, "explain");
+            }
+            let (x, path) = ts.explain().ok_or("no explanation")?;
+            expect((path[0], *path.last().unwrap()), ((x, true), (x, true)), "path ends")?;
+            expect(path.contains(&(x, false)), true, "through the negation")?;
+            expect(path.iter().all(|&(y, _)| y < n), true, "variables of the caller")?;
+            // the variables of the caller are the first n of the graph here
+            let G = ts.implication_graph();
+            for p in path.windows(2) {
+                let (u, v) = (2 * p[0].0 + p[0].1 as usize, 2 * p[1].0 + p[1].1 as usize);
+                expect(bfs_naive(G, u)[v] != usize::MAX, true, "implication")?;
+            }
+            Ok(())
+        },
+    );
+}
+
+// the smallest vertex connected to each vertex, without the vertex skip
+fn components_naive(n: usize, edges: &[(usize, usize)], skip: Option<usize>) -> Vec<usize> {
+    let mut label: Vec<usize> = (0..n).collect();
+    for _ in 0..n {
+        for &(u, v) in edges {
+            if skip == Some(u) || skip == Some(v) { continue; }
+            let m = label[u].min(label[v]);
+            label[u] = m;
+            label[v] = m;
+        }
+    }
+    label
+}
+
+fn bfs_naive(G: &Graph<()>, s: usize) -> Vec<usize> {
+    let mut dist = vec![usize::MAX; G.len()];
+    dist[s] = 0;
+    let mut queue = std::collections::VecDeque::from([s]);
+    while let Some(u) = queue.pop_front() {
+        for &(v, _) in G.neighbors(u) {
+            if dist[v] == usize::MAX {
+                dist[v] = dist[u] + 1;
+                queue.push_back(v);
+            }
+        }
+    }
+    dist
+}
+
+#[test]
+fn lowlink_components_match_naive() {
+    check(
+        "lowlink_components",
+        |rng| {
+            let n = rng.gen_range(1..10);
+            let m = rng.gen_range(0..2 * n);
+            let mut edges: Vec<(usize, usize)> = (0..m).map(|_| (rng.gen_range(0..n), rng.gen_range(0..n))).collect();
+            // parallel edges, in either direction
+            for i in 0..m {
+                if rng.gen_bool(0.2) {
+                    let (u, v) = edges[i];
+                    edges.push(if rng.gen() { (u, v) } else { (v, u) });
+                }
+            }
+            edges.shuffle(rng);
+            (n, edges)
+        },
+        |&n, edges: &[(usize, usize)]| {
+            let E: Vec<_> = edges.iter().map(|&(u, v)| (u, v, ())).collect();
+            let lowlink = LowLink::from_graph(&Graph::from_undirected_edges(n, &E));
+            let label = components_naive(n, edges, None);
+            let is_bridge = |i: usize| {
+                let rest: Vec<_> = edges.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, &e)| e).collect();
+                let l = components_naive(n, &rest, None);
+                l[edges[i].0] != l[edges[i].1]
+            };
+            let bridges: Vec<usize> = (0..edges.len()).filter(|&i| is_bridge(i)).collect();
+            let want: std::collections::BTreeSet<_> = bridges.iter().map(|&i| (edges[i].0.min(edges[i].1), edges[i].0.max(edges[i].1))).collect();
+            expect(&lowlink.bridges, &want, "bridges")?;
+
+            let (cmp, tree) = lowlink.two_edge_connected();
+            let rest: Vec<_> = (0..edges.len()).filter(|i| !bridges.contains(i)).map(|i| edges[i]).collect();
+            let two_edge = components_naive(n, &rest, None);
+            expect(tree.edges().count(), 2 * bridges.len(), "bridge tree edges")?;
+            for u in 0..n {
+                let dist = bfs_naive(&tree, cmp[u]);
+                for v in 0..n {
+                    expect(cmp[u] == cmp[v], two_edge[u] == two_edge[v], "two edge connected")?;
+                    if label[u] != label[v] { continue; }
+                    let separating = bridges.iter().filter(|&&i| {
+                        let rest: Vec<_> = edges.iter().enumerate().filter(|&(j, _)| j != i).map(|(_, &e)| e).collect();
+                        let l = components_naive(n, &rest, None);
+                        l[u] != l[v]
+                    }).count();
+                    expect(dist[cmp[v]], separating, "bridges on the path")?;
+                }
+            }
+
+            let (blocks, tree) = lowlink.biconnected();
+            expect(tree.len(), n + blocks.len(), "block cut tree size")?;
+            for &(u, v) in edges {
+                expect(blocks.iter().any(|b| b.contains(&u) && b.contains(&v)), true, "edge in a block")?;
+            }
+            for v in 0..n {
+                expect(blocks.iter().filter(|b| b.contains(&v)).count() >= 2, lowlink.aps.contains(&v), "aps")?;
+            }
+            let dist: Vec<_> = (0..n).map(|u| bfs_naive(&tree, u)).collect();
+            for w in 0..n {
+                let label_w = components_naive(n, edges, Some(w));
+                for u in (0..n).filter(|&u| u != w) {
+                    for v in (0..n).filter(|&v| v != w && label[v] == label[u]) {
+                        let on_path = dist[u][w].saturating_add(dist[w][v]) == dist[u][v];
+                        expect(on_path, label_w[u] != label_w[v], "separating vertex")?;
+                    }
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+// reach[u][v] is true if there is a path from u to v, possibly empty
+fn reach_naive(n: usize, edges: &[(usize, usize)]) -> Vec<Vec<bool>> {
+    let mut reach: Vec<Vec<bool>> = (0..n).map(|u| (0..n).map(|v| u == v).collect()).collect();
+    for &(u, v) in edges {
+        reach[u][v] = true;
+    }
+    for k in 0..n {
+        for i in 0..n {
+            for j in 0..n {
+                reach[i][j] |= reach[i][k] && reach[k][j];
+            }
+        }
+    }
+    reach
+}
+
+#[test]
+fn scc_order_and_reachability() {
+    check(
+        "scc_reachability",
+        |rng| {
+            let n = rng.gen_range(1..12);
+            // mostly forward edges, so that DAGs come up as well
+            let m = rng.gen_range(0..2 * n);
+            let p = rng.gen_range(0.0..0.3);
+            (n, (0..m).map(|_| {
+                let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                if u < v || rng.gen_bool(p) { (u, v) } else { (v, u) }
+            }).collect())
+        },
+        |&n, edges: &[(usize, usize)]| {
+            let reach = reach_naive(n, edges);
+            let G = Graph::from_edges(n, &edges.iter().map(|&(u, v)| (u, v, ())).collect::<Vec<_>>());
+            let mut scc = SCC::from_graph(&G);
+            let cmp = scc.cmp().clone();
+            for &(u, v) in edges {
+                expect(cmp[u] <= cmp[v], true, "topological order")?;
+            }
+            for u in 0..n {
+                for v in 0..n {
+                    expect(cmp[u] == cmp[v], reach[u][v] && reach[v][u], "same group")?;
+                }
+            }
+            // the edges of the graph stay after add_edge
+            let half = edges.len() / 2;
+            let mut grown = SCC::from_graph(&Graph::from_edges(n, &edges[..half].iter().map(|&(u, v)| (u, v, ())).collect::<Vec<_>>()));
+            for &(u, v) in &edges[half..] {
+                grown.add_edge(u, v);
+            }
+            let grown = grown.cmp();
+            for u in 0..n {
+                for v in 0..n {
+                    expect(grown[u] == grown[v], cmp[u] == cmp[v], "same group after add_edge")?;
+                }
+            }
+            let dag = scc.condensation();
+            for c in 0..dag.len() {
+                let mut next: Vec<_> = dag.neighbors(c).iter().map(|&(d, _)| d).collect();
+                expect(next.iter().all(|&d| c < d), true, "forward")?;
+                next.dedup();
+                expect(next.len(), dag.neighbors(c).len(), "duplicate edges")?;
+            }
+
+            let closure = Reachability::from_graph(&G);
+            let got: Vec<Vec<bool>> = (0..n).map(|u| (0..n).map(|v| closure.reachable(u, v)).collect()).collect();
+            expect(&got, &reach, "reachable")?;
+
+            let cyclic = edges.iter().any(|&(u, v)| reach[v][u]);
+            match topological_sort(&G) {
+                None => expect(cyclic, true, "cyclic"),
+                Some(order) => {
+                    let mut pos = vec![n; n];
+                    for (i, &u) in order.iter().enumerate() {
+                        pos[u] = i;
+                    }
+                    expect(pos.contains(&n), false, "permutation")?;
+                    expect(edges.iter().all(|&(u, v)| pos[u] < pos[v]), true, "order")
+                },
+            }
+        },
+    );
+}
+
+// the size of a maximum matching by a bit dp over the vertices
+fn max_matching_naive(n: usize, edges: &[(usize, usize)]) -> usize {
+    let mut dp = vec![0; 1 << n];
+    for S in 1..1usize << n {
+        let u = S.trailing_zeros() as usize;
+        dp[S] = dp[S & !(1 << u)];
+        for &(a, b) in edges {
+            let v = if a == u { b } else if b == u { a } else { continue };
+            if v != u && S >> v & 1 == 1 {
+                dp[S] = dp[S].max(dp[S & !(1 << u) & !(1 << v)] + 1);
+            }
+        }
+    }
+    dp[(1 << n) - 1]
+}
+
+#[test]
+fn bipartite_matching_matches_flow() {
+    check(
+        "bipartite_matching",
+        |rng| {
+            let (l, r) = (rng.gen_range(1..8), rng.gen_range(1..8));
+            let m = rng.gen_range(0..2 * (l + r));
+            ((l, r), (0..m).map(|_| (rng.gen_range(0..l), rng.gen_range(0..r))).collect())
+        },
+        |&(l, r), edges: &[(usize, usize)]| {
+            let mut fg = FlowGraph::new(l + r + 2);
+            for u in 0..l {
+                fg.add_edge(l + r, u, 1);
+            }
+            for v in 0..r {
+                fg.add_edge(l + v, l + r + 1, 1);
+            }
+            for &(u, v) in edges {
+                fg.add_edge(u, l + v, 1);
+            }
+            let want = fg.flow(l + r, l + r + 1) as usize;
+
+            let mut bm = BipartiteMatching::new(l, r);
+            for &(u, v) in edges {
+                bm.add_edge(u, v);
+            }
+            expect(bm.solve(), want, "size")?;
+            let pairs = bm.pairs();
+            expect(pairs.len(), want, "pairs")?;
+            for &p in &pairs {
+                expect(edges.contains(&p), true, "edge")?;
+            }
+            let mut us: Vec<_> = pairs.iter().map(|p| p.0).collect();
+            let mut vs: Vec<_> = pairs.iter().map(|p| p.1).collect();
+            us.dedup();
+            vs.sort();
+            vs.dedup();
+            expect((us.len(), vs.len()), (want, want), "disjoint")?;
+
+            let (left, right) = bm.vertex_cover();
+            expect(left.iter().chain(&right).filter(|&&b| b).count(), want, "cover size")?;
+            for &(u, v) in edges {
+                expect(left[u] || right[v], true, "covered")?;
+            }
+            let G = Graph::from_undirected_edges(l + r, &edges.iter().map(|&(u, v)| (u, l + v, ())).collect::<Vec<_>>());
+            expect(max_matching(&G).iter().flatten().count(), 2 * want, "blossom")
+        },
+    );
+}
+
+#[test]
+fn max_matching_matches_naive() {
+    check(
+        "max_matching",
+        |rng| {
+            let n = rng.gen_range(1..13);
+            let m = rng.gen_range(0..2 * n);
+            (n, (0..m).map(|_| (rng.gen_range(0..n), rng.gen_range(0..n))).collect())
+        },
+        |&n, edges: &[(usize, usize)]| {
+            let G = Graph::from_undirected_edges(n, &edges.iter().map(|&(u, v)| (u, v, ())).collect::<Vec<_>>());
+            let mate = max_matching(&G);
+            for (u, &v) in mate.iter().enumerate() {
+                if let Some(v) = v {
+                    expect(mate[v], Some(u), "mutual")?;
+                    expect(edges.contains(&(u, v)) || edges.contains(&(v, u)), true, "edge")?;
+                    expect(u != v, true, "loop")?;
+                }
+            }
+            expect(mate.iter().flatten().count(), 2 * max_matching_naive(n, edges), "size")
+        },
+    );
+}
+
+#[test]
+fn hungarian_matches_min_cost_flow() {
+    check(
+        "hungarian",
+        |rng| {
+            let n = rng.gen_range(0..6);
+            let m = n + rng.gen_range(0..3);
+            ((n, m), (0..n * m).map(|_| rng.gen_range(-20..50)).collect())
+        },
+        |&(n, m), a: &[i64]| {
+            if a.len() < n * m { return Ok(()); }
+            let a: Vec<Vec<i64>> = (0..n).map(|i| a[i * m..(i + 1) * m].to_vec()).collect();
+            let mut fg = CostFlowGraph::new(n + m + 2);
+            for (i, row) in a.iter().enumerate() {
+                fg.add_edge(n + m, i, 1, 0);
+                for (j, &c) in row.iter().enumerate() {
+                    fg.add_edge(i, n + j, 1, c);
+                }
+            }
+            for j in 0..m {
+                fg.add_edge(n + j, n + m + 1, 1, 0);
+            }
+            let want = fg.min_cost(n + m, n + m + 1, n as i64).unwrap();
+
+            let (cost, assign) = hungarian(&a);
+            expect(cost, want, "cost")?;
+            expect((0..n).map(|i| a[i][assign[i]]).sum::<i64>(), want, "assignment")?;
+            let mut cols = assign.clone();
+            cols.sort();
+            cols.dedup();
+            expect(cols.len(), n, "distinct")?;
+
+            let b: Vec<Vec<f64>> = a.iter().map(|row| row.iter().map(|&x| x as f64 / 4.0).collect()).collect();
+            let (cost, _) = hungarian(&b);
+            expect((cost - want as f64 / 4.0).abs() < 1e-9, true, "f64")
+        },
+    );
+}
+
+const MOD: u64 = 998_244_353;
+
+// the composition of x -> a * x + b along a path, which depends on the direction,
+// together with (sum, length) so that an addition can act on it
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct PathVal { a: u64, b: u64, sum: i64, len: i64 }
+impl Monoid for PathVal {
+    fn one() -> Self { PathVal { a: 1, b: 0, sum: 0, len: 0 } }
+    fn ope(&self, rhs: &Self) -> Self {
+        PathVal { a: self.a * rhs.a % MOD, b: (self.b * rhs.a + rhs.b) % MOD, sum: self.sum + rhs.sum, len: self.len + rhs.len }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Add(i64);
+impl Monoid for Add {
+    fn one() -> Self { Add(0) }
+    fn ope(&self, rhs: &Self) -> Self { Add(self.0 + rhs.0) }
+}
+impl Act<PathVal> for Add {
+    fn act(&self, x: PathVal) -> PathVal { PathVal { sum: x.sum + self.0 * x.len, ..x } }
+}
+
+fn random_path_val(rng: &mut impl Rng) -> PathVal {
+    PathVal { a: rng.gen_range(0..MOD), b: rng.gen_range(0..MOD), sum: rng.gen_range(-10..10), len: 1 }
+}
+
+// a random tree as the parent of each vertex but 0
+fn random_tree(rng: &mut impl Rng, n: usize) -> Vec<(usize, usize)> {
+    (1..n).map(|v| (rng.gen_range(0..v), v)).collect()
+}
+
+// the vertices on the path from u to v on the rooted tree of hld
+fn path_naive(hld: &HLD, u: usize, v: usize) -> Vec<usize> {
+    let w = hld.lca(u, v);
+    let mut up = vec![u];
+    while *up.last().unwrap() != w { up.push(hld.tree.parent(*up.last().unwrap()).unwrap()); }
+    let mut down = vec![];
+    let mut x = v;
+    while x != w { down.push(x); x = hld.tree.parent(x).unwrap(); }
+    up.extend(down.into_iter().rev());
+    up
+}
+
+#[derive(Debug, Clone)]
+enum StaticTreeOp {
+    Lca(usize, usize),
+    Fold(usize, usize),
+    Apply(usize, usize, i64),
+    Set(usize, PathVal),
+}
+
+#[test]
+fn link_cut_tree_matches_hld() {
+    check(
+        "link_cut_tree_matches_hld",
+        |rng| {
+            let n = rng.gen_range(1..30);
+            let mut edges = random_tree(rng, n);
+            edges.shuffle(rng);
+            let vals: Vec<PathVal> = (0..n).map(|_| random_path_val(rng)).collect();
+            let ops = (0..100).map(|_| {
+                let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                match rng.gen_range(0..4) {
+                    0 => StaticTreeOp::Lca(u, v),
+                    1 => StaticTreeOp::Fold(u, v),
+                    2 => StaticTreeOp::Apply(u, v, rng.gen_range(-10..10)),
+                    _ => StaticTreeOp::Set(u, random_path_val(rng)),
+                }
+            }).collect();
+            ((n, edges, vals), ops)
+        },
+        |(n, edges, vals), ops| {
+            let hld = HLD::new(0, *n, edges);
+            let mut naive = vals.clone();
+            let mut lct = LinkCutTree::<PathVal, Add>::from(vals.clone());
+            for &(u, v) in edges {
+                lct.link(u, v);
+            }
+            for op in ops {
+                match *op {
+                    StaticTreeOp::Lca(u, v) => {
+                        lct.evert(0);
+                        expect(lct.lca(u, v), Some(hld.lca(u, v)), "lca")?;
+                    },
+                    StaticTreeOp::Fold(u, v) => {
+                        let want = path_naive(&hld, u, v).iter().fold(PathVal::one(), |acc, &x| acc.ope(&naive[x]));
+                        expect(lct.path_fold(u, v), want, "path_fold")?;
+                    },
+                    StaticTreeOp::Apply(u, v, x) => {
+                        for w in path_naive(&hld, u, v) { naive[w] = Add(x).act(naive[w]); }
+                        lct.path_apply(u, v, Add(x));
+                    },
+                    StaticTreeOp::Set(u, x) => { naive[u] = x; lct.set(u, x); },
+                }
+            }
+            for (v, &x) in naive.iter().enumerate() {
+                expect(lct.get(v), x, "get")?;
+            }
+            lct.evert(0);
+            for v in 0..*n {
+                expect(lct.parent(v), hld.tree.parent(v), "parent")?;
+            }
+            Ok(())
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum ForestOp {
+    Link(usize, usize),
+    // cuts the k-th edge
+    Cut(usize),
+    Fold(usize, usize),
+}
+
+#[test]
+fn link_cut_tree_forest() {
+    check(
+        "link_cut_tree_forest",
+        |rng| {
+            let n = rng.gen_range(1..20);
+            let vals: Vec<PathVal> = (0..n).map(|_| random_path_val(rng)).collect();
+            let ops = (0..100).map(|_| {
+                let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                match rng.gen_range(0..3) {
+                    0 => ForestOp::Link(u, v),
+                    1 => ForestOp::Cut(rng.gen_range(0..100)),
+                    _ => ForestOp::Fold(u, v),
+                }
+            }).collect();
+            (vals, ops)
+        },
+        |vals, ops| {
+            let n = vals.len();
+            let mut edges: Vec<(usize, usize)> = vec![];
+            let mut lct = LinkCutTree::<PathVal, Add>::from(vals.clone());
+            // the path from u to v by a search from v, if connected
+            let path = |edges: &[(usize, usize)], u: usize, v: usize| -> Option<Vec<usize>> {
+                let mut prev = vec![None; n];
+                prev[v] = Some(v);
+                let mut stack = vec![v];
+                while let Some(x) = stack.pop() {
+                    for &(a, b) in edges {
+                        for (s, t) in [(a, b), (b, a)] {
+                            if s == x && prev[t].is_none() { prev[t] = Some(x); stack.push(t); }
+                        }
+                    }
+                }
+                prev[u]?;
+                let mut ret = vec![u];
+                while *ret.last().unwrap() != v { ret.push(prev[*ret.last().unwrap()].unwrap()); }
+                Some(ret)
+            };
+            for op in ops {
+                match *op {
+                    ForestOp::Link(u, v) => {
+                        let connected = path(&edges, u, v).is_some();
+                        expect(lct.connected(u, v), connected, "connected")?;
+                        if !connected { edges.push((u, v)); lct.link(u, v); }
+                    },
+                    ForestOp::Cut(k) if !edges.is_empty() => {
+                        let (u, v) = edges.remove(k % edges.len());
+                        lct.cut(v, u);
+                    },
+                    ForestOp::Fold(u, v) => {
+                        let p = path(&edges, u, v);
+                        expect(lct.connected(u, v), p.is_some(), "connected")?;
+                        if let Some(p) = p {
+                            let want = p.iter().fold(PathVal::one(), |acc, &x| acc.ope(&vals[x]));
+                            expect(lct.path_fold(u, v), want, "path_fold")?;
+                        }
+                    },
+                    _ => {},
+                }
+            }
+            Ok(())
+        },
+    );
+}
+
+#[derive(Debug, Clone)]
+enum HLDOp {
+    // folds the path by path_intervals in order, and the sums by path_fold
+    Fold(usize, usize, bool),
+    Update(usize, usize, i64, bool),
+    Subtree(usize),
+}
+
+#[test]
+fn hld_path_queries() {
+    check(
+        "hld_path_queries",
+        |rng| {
+            let n = rng.gen_range(1..30);
+            // weights on the edges, put on the children
+            let edges: Vec<(usize, usize, PathVal)> = random_tree(rng, n).into_iter()
+                .map(|(p, v)| (p, v, random_path_val(rng)))
+                .collect();
+            let vals: Vec<PathVal> = (0..n).map(|_| random_path_val(rng)).collect();
+            let ops = (0..100).map(|_| {
+                let (u, v) = (rng.gen_range(0..n), rng.gen_range(0..n));
+                match rng.gen_range(0..3) {
+                    0 => HLDOp::Fold(u, v, rng.gen_bool(0.5)),
+                    1 => HLDOp::Update(u, v, rng.gen_range(-10..10), rng.gen_bool(0.5)),
+                    _ => HLDOp::Subtree(u),
+                }
+            }).collect();
+            ((edges, vals), ops)
+        },
+        |(edges, vals), ops| {
+            let n = vals.len();
+            let tree = DirectedTree::new(0, n, edges);
+            let hld = HLD::from_weighted(&tree);
+            // naive[0] on the vertices, naive[1] on the children of the edges
+            let mut naive = [vals.clone(), vals.clone()];
+            for &(_, v, w) in edges { naive[1][v] = w; }
+            naive[1][0] = PathVal::one();
+            let mut seq = [hld.sequence().map(|&v| vals[v]).collect::<Vec<_>>(), hld.edge_seq(&tree, PathVal::one())];
+            let mut lazy = [PathLazySegTree::<PathVal, Add>::from(seq[0].clone()), PathLazySegTree::<PathVal, Add>::from(seq[1].clone())];
+            // the initial values, since these are never updated
+            let initial = [naive[0].clone(), naive[1].clone()];
+            let seg = [PathSegTree::from(seq[0].clone()), PathSegTree::from(seq[1].clone())];
+            let sums: [Vec<i64>; 2] = [
+                seq[0].iter().map(|x| x.sum).collect(),
+                seq[1].iter().map(|x| x.sum).collect(),
+            ];
+            let sum_seg = [SegTree::from(sums[0].clone()), SegTree::from(sums[1].clone())];
+            for op in ops {
+                match *op {
+                    HLDOp::Fold(u, v, edge) => {
+                        let e = edge as usize;
+                        let mut path = path_naive(&hld, u, v);
+                        if edge {
+                            // the edges are on the path but the lca
+                            let w = hld.lca(u, v);
+                            path.retain(|&x| x != w);
+                        }
+                        let want = path.iter().fold(PathVal::one(), |acc, &x| acc.ope(&naive[e][x]));
+                        let got = hld.path_intervals(u, v, edge).iter().fold(PathVal::one(), |acc, &(l, r, rev)| {
+                            if rev {
+                                seq[e][l..r].iter().rev().fold(acc, |acc, x| acc.ope(x))
+                            } else {
+                                seq[e][l..r].iter().fold(acc, |acc, x| acc.ope(x))
+                            }
+                        });
+                        expect(got, want, "path_intervals")?;
+                        expect(hld.path_fold_lazy(&mut lazy[e], u, v, edge), want, "path_fold_lazy")?;
+                        let want = path.iter().fold(PathVal::one(), |acc, &x| acc.ope(&initial[e][x]));
+                        expect(hld.path_fold(&seg[e], u, v, edge), want, "path_fold")?;
+                        let want = path.iter().map(|&x| sums[e][hld.index(x)]).sum::<i64>();
+                        expect(hld.path_fold_commutative(&sum_seg[e], u, v, edge), want, "path_fold_commutative")?;
+                    },
+                    HLDOp::Update(u, v, x, edge) => {
+                        let e = edge as usize;
+                        let w = hld.lca(u, v);
+                        for y in path_naive(&hld, u, v) {
+                            if edge && y == w { continue; }
+                            naive[e][y] = Add(x).act(naive[e][y]);
+                            seq[e][hld.index(y)] = naive[e][y];
+                        }
+                        hld.path_update(&mut lazy[e], u, v, Add(x), edge);
+                    },
+                    HLDOp::Subtree(v) => {
+                        let (l, r) = hld.subtree_interval(v);
+                        let mut got: Vec<usize> = hld.seq[l..r].to_vec();
+                        got.sort();
+                        let want: Vec<usize> = (0..n).filter(|&x| {
+                            let mut y = x;
+                            while y != v { match hld.tree.parent(y) { Some(p) => y = p, None => return false } }
+                            true
+                        }).collect();
+                        expect(got, want, "subtree_interval")?;
+                    },
+                }
+            }
+            for e in 0..2 {
+                expect(lazy[e].iter().map(|x| x.sum).collect::<Vec<_>>(), seq[e].iter().map(|x| x.sum).collect(), "sums")?;
+            }
+            Ok(())
+        },
+    );
+}
+
+// (sum of distances, the number of vertices)
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct DistSum(i64, i64);
+impl Monoid for DistSum {
+    fn one() -> Self { DistSum(0, 0) }
+    fn ope(&self, rhs: &Self) -> Self { DistSum(self.0 + rhs.0, self.1 + rhs.1) }
+}
+
+// the farthest distance
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Far(i64);
+impl Monoid for Far {
+    fn one() -> Self { Far(0) }
+    fn ope(&self, rhs: &Self) -> Self { Far(self.0.max(rhs.0)) }
+}
+
+#[test]
+fn rerooting_matches_naive() {
+    check(
+        "rerooting_matches_naive",
+        |rng| {
+            let n = rng.gen_range(1..30);
+            let root = rng.gen_range(0..n);
+            let edges: Vec<(usize, usize, i64)> = random_tree(rng, n).into_iter()
+                .map(|(p, v)| (p, v, rng.gen_range(1..10)))
+                .collect();
+            ((n, root), edges)
+        },
+        |&(n, root), edges| {
+            let tree = DirectedTree::new(root, n, &edges.to_vec());
+            // distances from every vertex
+            let mut dist = vec![vec![i64::MAX; n]; n];
+            for (s, d) in dist.iter_mut().enumerate() {
+                d[s] = 0;
+                for _ in 0..n {
+                    for &(u, v, w) in edges {
+                        if d[u] != i64::MAX { d[v] = d[v].min(d[u] + w); }
+                        if d[v] != i64::MAX { d[u] = d[u].min(d[v] + w); }
+                    }
+                }
+            }
+            let sums = rerooting(&tree, |x: DistSum, w| DistSum(x.0 + w * x.1, x.1), |x, _| DistSum(x.0, x.1 + 1));
+            let want: Vec<DistSum> = dist.iter().map(|d| DistSum(d.iter().sum(), n as i64)).collect();
+            expect(sums, want, "sum of distances")?;
+            let far = rerooting(&tree, |x: Far, w| Far(x.0 + w), |x, _| x);
+            let want: Vec<Far> = dist.iter().map(|d| Far(*d.iter().max().unwrap())).collect();
+            expect(far, want, "farthest")?;
+            // put_vertex gets the vertex, e.g. the sum of vertex ids over the tree is the same for every root
+            let ids = rerooting(&tree, |x: DistSum, _| x, |x, v| DistSum(x.0 + v as i64, 0));
+            expect(ids, vec![DistSum((n * (n - 1) / 2) as i64, 0); n], "vertex ids")?;
+            Ok(())
+        },
+    );
+}
+
+// a path graph of a million vertices would overflow the stack of any recursive traversal
+#[test]
+fn long_path_graph() {
+    const N: usize = 1_000_000;
+    let run = || {
+        let E: Vec<(usize, usize)> = (1..N).map(|v| (v - 1, v)).collect();
+        let mut G = vec![vec![]; N];
+        for &(u, v) in &E {
+            G[u].push(v);
+            G[v].push(u);
+        }
+
+        let tree = DirectedTreeNorm::new(0, N, &E);
+        assert_eq!(tree.parent(N - 1), Some(N - 2));
+        let weighted = DirectedTree::new(N - 1, N, &E.iter().map(|&(u, v)| (u, v, 1_i64)).collect());
+        assert_eq!(weighted.parent(0), Some((1, 1)));
+
+        let hld = HLD::from(&tree);
+        assert!(hld.seq.iter().copied().eq(0..N));
+        assert_eq!(hld.lca(N - 1, N / 2), N / 2);
+        assert_eq!(hld.path_intervals(N - 1, 0, false), vec![(0, N, true)]);
+
+        let lca = LCA::new(N, 0, G.clone(), 20);
+        assert_eq!(lca.lca(N - 1, N / 2), N / 2);
+        assert_eq!(lca.distance(0, N - 1), N - 1);
+
+        let diameter = Diameter::new(&G);
+        assert_eq!(diameter.D, N - 1);
+
+        let lowlink = LowLink::new(&G);
+        assert_eq!(lowlink.bridges.len(), N - 1);
+        assert_eq!(lowlink.aps.len(), N - 2);
+        assert_eq!(lowlink.ord[N - 1], N - 1);
+        assert_eq!(lowlink.two_edge_connected().1.len(), N);
+        assert_eq!(lowlink.biconnected().0.len(), N - 1);
+
+        let mut scc = SCC::from(N, E.clone());
+        assert_eq!(scc.dag().len(), N);
+
+        let mut fg = FlowGraph::new(N);
+        for &(u, v) in &E {
+            fg.add_edge(u, v, 2 + (v % 3) as i64);
+        }
+        assert_eq!(fg.flow(0, N - 1), 2);
+        assert_eq!(fg.min_cut(0).iter().filter(|&&b| b).count(), 3);
+
+        let far = rerooting(&weighted, |x: Far, w| Far(x.0 + w), |x, _| x);
+        assert_eq!(far[0], Far(N as i64 - 1));
+        assert_eq!(far[N / 2], Far((N / 2).max(N - 1 - N / 2) as i64));
+    };
+    // the usual 2 MiB of a spawned thread
+    std::thread::Builder::new().stack_size(2 << 20).spawn(run).unwrap().join().unwrap();
+}